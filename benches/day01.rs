@@ -0,0 +1,72 @@
+//! Compares day 1 against the byte by byte scan and the uncached decoder it
+//! replaced, on the real input and on a generated one of a million lines.
+//!
+//! `cargo bench --bench day01` prints the median of each and the speed-up.
+
+use std::{hint::black_box, time::Duration};
+
+use aoc_2023::{
+    bench::{self, Stage},
+    day01::{self, Day01, Decoder, DigitVocabulary},
+    generate, Solution,
+};
+use bstr::ByteSlice;
+
+/// Sum of the first and last digit of each line, looking at one byte at a
+/// time from both ends.
+fn calibration_sum_bytewise(input: &[u8]) -> u64 {
+    input
+        .lines()
+        .map(|line| {
+            let digit = |c: &u8| c.is_ascii_digit().then(|| u64::from(c - b'0'));
+            let first = line.iter().find_map(digit).unwrap_or(0);
+            let last = line.iter().rev().find_map(digit).unwrap_or(0);
+            first * 10 + last
+        })
+        .sum()
+}
+
+/// Part 2 with a decoder built for every call.
+fn spelled_calibration_sum_uncached(input: &[u8]) -> u64 {
+    Decoder::new(DigitVocabulary::english()).sum(input)
+}
+
+fn median(stage: Stage, f: impl FnMut() -> u64) -> Duration {
+    let measurement = bench::measure(Day01::DAY, stage, &bench::Options::default(), f);
+    Duration::from_nanos(measurement.median_ns)
+}
+
+fn compare(name: &str, stage: Stage, before: impl FnMut() -> u64, after: impl FnMut() -> u64) {
+    let (before, after) = (median(stage, before), median(stage, after));
+    println!(
+        "{name:<10} {stage:<5} {before:>12.1?} -> {after:>12.1?}  {:.2}x",
+        before.as_secs_f64() / after.as_secs_f64()
+    );
+}
+
+fn main() {
+    let generated = generate::generator(Day01::DAY)
+        .unwrap()
+        .generate(1_000_000, 0)
+        .unwrap();
+    let inputs = Day01::INPUT
+        .map(|input| ("real", input))
+        .into_iter()
+        .chain([("1M lines", generated.as_bytes())]);
+    for (name, input) in inputs {
+        let part_1 = day01::part_1(input).unwrap();
+        assert_eq!(calibration_sum_bytewise(input), part_1);
+        compare(
+            name,
+            Stage::Part1,
+            || calibration_sum_bytewise(black_box(input)),
+            || day01::part_1(black_box(input)).unwrap(),
+        );
+        compare(
+            name,
+            Stage::Part2,
+            || spelled_calibration_sum_uncached(black_box(input)),
+            || day01::part_2(black_box(input)).unwrap(),
+        );
+    }
+}
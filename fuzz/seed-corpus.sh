@@ -0,0 +1,9 @@
+#!/bin/sh
+# Seeds the corpus of every fuzz target with the examples of its day.
+set -e
+cd "$(dirname "$0")"
+for examples in ../examples/day*; do
+    day=$(basename "$examples")
+    mkdir -p "corpus/$day"
+    cp "$examples"/*.txt "corpus/$day/"
+done
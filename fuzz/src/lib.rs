@@ -0,0 +1,19 @@
+//! Fuzz targets for the parsers and solutions of every day.
+//!
+//! With [cargo-fuzz](https://github.com/rust-fuzz/cargo-fuzz) installed, seed
+//! the corpus with the examples and fuzz a day from the `fuzz` directory:
+//!
+//! ```sh
+//! ./seed-corpus.sh
+//! cargo +nightly fuzz run day08
+//! ```
+
+/// Parses `input` as the input of `day` and solves both parts.
+///
+/// Malformed inputs have to be rejected with a [`ParseError`], anything that
+/// makes this panic is a bug.
+///
+/// [`ParseError`]: aoc_2023::ParseError
+pub fn run(day: u8, input: &[u8]) {
+    let _ = aoc_2023::puzzle(day).unwrap().run(input, None);
+}
@@ -0,0 +1,187 @@
+//! Counts the heap allocations of the solutions, with the `alloc-stats`
+//! feature.
+//!
+//! The feature wraps the global mimalloc allocator in [`Counting`], which
+//! sees the bytes the solutions request from the heap. The resident memory
+//! of the process comes from mimalloc. The counters are global, so
+//! measurements are only meaningful while a single thread allocates.
+
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    fmt,
+    ptr,
+    sync::atomic::{AtomicU64, Ordering::Relaxed},
+};
+
+use mimalloc_rust::raw::extended_functions::mi_process_info;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// A global allocator that counts what it forwards to `A`.
+pub struct Counting<A>(pub A);
+
+impl<A> Counting<A> {
+    fn grow(size: usize) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        ALLOCATED_BYTES.fetch_add(size as u64, Relaxed);
+        let live = LIVE_BYTES.fetch_add(size as u64, Relaxed) + size as u64;
+        PEAK_BYTES.fetch_max(live, Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        LIVE_BYTES.fetch_sub(size as u64, Relaxed);
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Counting<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.0.alloc(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.0.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { self.0.dealloc(ptr, layout) };
+        Self::shrink(layout.size());
+    }
+
+    /// Counted as a new allocation of `new_size` bytes.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { self.0.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            Self::grow(new_size);
+            Self::shrink(layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Heap usage of a piece of code, see [`measure`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations and reallocations.
+    pub allocations: u64,
+    /// Total bytes requested, freed memory is not subtracted.
+    pub bytes: u64,
+    /// Most heap bytes alive at once, on top of what was alive before.
+    pub peak_live_bytes: u64,
+    /// Most memory the process had resident so far, including before. The
+    /// operating system only tracks the peak of the whole process.
+    pub peak_resident_bytes: u64,
+}
+
+/// Peak resident memory of the process, as mimalloc reports it.
+fn peak_resident_bytes() -> u64 {
+    let mut peak_rss = 0;
+    // SAFETY: mimalloc skips the null pointers and writes a `usize` to the
+    // other one.
+    unsafe {
+        mi_process_info(
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut peak_rss,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+        );
+    }
+    peak_rss as u64
+}
+
+/// Runs `f` and counts its heap allocations.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Relaxed);
+    let live = LIVE_BYTES.load(Relaxed);
+    PEAK_BYTES.store(live, Relaxed);
+
+    let result = f();
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        bytes: ALLOCATED_BYTES.load(Relaxed) - bytes,
+        peak_live_bytes: PEAK_BYTES.load(Relaxed).saturating_sub(live),
+        peak_resident_bytes: peak_resident_bytes(),
+    };
+    (result, stats)
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 MiB`.
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{value:.1} {}", UNITS[unit])
+    }
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak live heap, {} peak resident",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak_live_bytes),
+            Bytes(self.peak_resident_bytes)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (v, stats) = measure(|| {
+            let mut v = Vec::<u64>::with_capacity(128);
+            v.push(1);
+            drop(Vec::<u8>::with_capacity(4096));
+            v
+        });
+        assert_eq!(v, [1]);
+        assert!(stats.allocations >= 2);
+        // Other tests allocate concurrently, so the peak is not predictable.
+        assert!(stats.bytes >= 1024 + 4096);
+        assert!(stats.peak_resident_bytes > 0);
+    }
+
+    #[test]
+    fn test_display() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 1536,
+            peak_live_bytes: 100,
+            peak_resident_bytes: 3 << 20,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 allocations, 1.5 KiB allocated, 100 B peak live heap, 3.0 MiB peak resident"
+        );
+    }
+}
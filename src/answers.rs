@@ -0,0 +1,304 @@
+//! Registry of known answers, used to check that a change still solves the
+//! real inputs.
+//!
+//! The registry is a TOML file with a table per day and input id:
+//!
+//! ```toml
+//! [day01.default]
+//! part1 = 53386
+//! part2 = "53312"
+//!
+//! [day01.friend]
+//! input = "inputs/day-01-friend.txt"
+//! part1 = 54159
+//! ```
+//!
+//! `input` is relative to the directory of the registry and defaults to the
+//! input in the repository, `src/day-NN/input.txt`. Answers can be numbers or
+//! strings, missing parts are reported as such.
+//!
+//! The example corpus in `examples/` uses the same format: every
+//! `examples/dayNN/<id>.txt` has its answers in `<id>.toml` next to it,
+//! without the `input` key. Parts without an answer are not run, as examples
+//! often only fit one part. See [`read_examples`].
+
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fmt, fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+
+use crate::{Part, Puzzle};
+
+/// The known answers for one input of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u8,
+    pub input_id: String,
+    pub input: PathBuf,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawEntry {
+    input: Option<PathBuf>,
+    part1: Option<RawAnswer>,
+    part2: Option<RawAnswer>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawAnswer {
+    Number(i64),
+    Text(String),
+}
+
+impl RawAnswer {
+    fn into_string(self) -> String {
+        match self {
+            Self::Number(n) => n.to_string(),
+            Self::Text(s) => s,
+        }
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// The day of a key like `day05`.
+fn parse_day(key: &str) -> io::Result<u8> {
+    key.strip_prefix("day")
+        .and_then(|day| day.parse::<u8>().ok())
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| invalid(format!("`{key}` is not a day like `day05`")))
+}
+
+/// Parses a registry, resolving input paths relative to `base`.
+pub fn parse_registry(s: &str, base: &Path) -> io::Result<Vec<Entry>> {
+    let days: BTreeMap<String, BTreeMap<String, RawEntry>> =
+        toml::from_str(s).map_err(|err| invalid(err.to_string()))?;
+
+    let mut entries = Vec::new();
+    for (key, inputs) in days {
+        let day = parse_day(&key)?;
+
+        for (input_id, raw) in inputs {
+            let input = raw
+                .input
+                .unwrap_or_else(|| format!("src/day-{day:02}/input.txt").into());
+            entries.push(Entry {
+                day,
+                input_id,
+                input: base.join(input),
+                part_1: raw.part1.map(RawAnswer::into_string),
+                part_2: raw.part2.map(RawAnswer::into_string),
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Reads the example corpus in `dir`, every `dayNN/<id>.txt` with the answers
+/// in `dayNN/<id>.toml`.
+pub fn read_examples(dir: &Path) -> io::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    for day_dir in fs::read_dir(dir)? {
+        let day_dir = day_dir?.path();
+        let day = parse_day(&day_dir.file_name().unwrap_or_default().to_string_lossy())?;
+
+        for file in fs::read_dir(&day_dir)? {
+            let input = file?.path();
+            if input.extension() != Some(OsStr::new("txt")) {
+                continue;
+            }
+            let sidecar = input.with_extension("toml");
+            let in_sidecar = |message: String| invalid(format!("{}: {message}", sidecar.display()));
+            let raw: RawEntry = fs::read_to_string(&sidecar)
+                .map_err(|err| in_sidecar(err.to_string()))
+                .and_then(|s| toml::from_str(&s).map_err(|err| in_sidecar(err.to_string())))?;
+            if raw.input.is_some() {
+                return Err(in_sidecar(
+                    "the input of an example is the `.txt` file".to_string(),
+                ));
+            }
+
+            entries.push(Entry {
+                day,
+                input_id: input.file_stem().unwrap().to_string_lossy().into_owned(),
+                part_1: raw.part1.map(RawAnswer::into_string),
+                part_2: raw.part2.map(RawAnswer::into_string),
+                input,
+            });
+        }
+    }
+    entries.sort_by(|a, b| (a.day, &a.input_id).cmp(&(b.day, &b.input_id)));
+    Ok(entries)
+}
+
+/// Outcome of checking one part against the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong {
+        expected: String,
+        found: String,
+    },
+    /// No answer registered, `found` is what the solution returned.
+    Missing {
+        found: String,
+    },
+    /// The input did not parse or the solution panicked.
+    Failed(String),
+}
+
+impl Status {
+    /// Whether the check failed, a missing answer is not a failure.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Wrong { .. } | Self::Failed(_))
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => f.write_str("ok"),
+            Self::Wrong { expected, found } => write!(f, "expected {expected}, found {found}"),
+            Self::Missing { found } => write!(f, "no answer registered, found {found}"),
+            Self::Failed(reason) => write!(f, "failed: {reason}"),
+        }
+    }
+}
+
+/// The result of one part of an [`Entry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub part: Part,
+    pub status: Status,
+    /// Time spent parsing and solving the part.
+    pub time: Duration,
+}
+
+/// Solves every part of `puzzle` that exists on `input` and compares the
+/// answers with `entry`.
+pub fn check(puzzle: &dyn Puzzle, entry: &Entry, input: &[u8]) -> Vec<Check> {
+    [(Part::One, &entry.part_1), (Part::Two, &entry.part_2)]
+        .into_iter()
+        .filter_map(|(part, expected)| check_part(puzzle, part, expected.as_deref(), input))
+        .collect()
+}
+
+/// Like [`check`], but only solves the parts `entry` has an answer for.
+pub fn check_known(puzzle: &dyn Puzzle, entry: &Entry, input: &[u8]) -> Vec<Check> {
+    [(Part::One, &entry.part_1), (Part::Two, &entry.part_2)]
+        .into_iter()
+        .filter_map(|(part, expected)| check_part(puzzle, part, Some(expected.as_deref()?), input))
+        .collect()
+}
+
+/// `None` if the puzzle has no such part.
+fn check_part(
+    puzzle: &dyn Puzzle,
+    part: Part,
+    expected: Option<&str>,
+    input: &[u8],
+) -> Option<Check> {
+    let start = Instant::now();
+    let answers = panic::catch_unwind(AssertUnwindSafe(|| puzzle.run(input, Some(part))));
+    let time = start.elapsed();
+
+    let status = match answers {
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown reason");
+            Status::Failed(format!("panicked: {message}"))
+        }
+        Ok(Err(err)) => Status::Failed(err.to_string()),
+        Ok(Ok(answers)) => {
+            // Part 2 of day 25 has no puzzle.
+            let found = match part {
+                Part::One => answers.part_1,
+                Part::Two => answers.part_2,
+            }?;
+            match expected {
+                None => Status::Missing { found },
+                Some(expected) if expected == found => Status::Correct,
+                Some(expected) => Status::Wrong {
+                    expected: expected.to_string(),
+                    found,
+                },
+            }
+        }
+    };
+    Some(Check { part, status, time })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = r#"
+[day01.default]
+part1 = 142
+
+[day01.other]
+input = "inputs/one.txt"
+part1 = "1"
+part2 = 2
+
+[day25.default]
+part1 = 1
+"#;
+
+    #[test]
+    fn test_parse_registry() {
+        let entries = parse_registry(REGISTRY, Path::new("base")).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].input, Path::new("base/src/day-01/input.txt"));
+        assert_eq!(entries[0].part_1.as_deref(), Some("142"));
+        assert_eq!(entries[0].part_2, None);
+        assert_eq!(entries[1].input, Path::new("base/inputs/one.txt"));
+        assert_eq!(entries[2].day, 25);
+
+        assert!(parse_registry("[day26.default]", Path::new("")).is_err());
+        assert!(parse_registry("[day01.default]\npart3 = 1", Path::new("")).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let entries = parse_registry(REGISTRY, Path::new("")).unwrap();
+        let input = b"1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        let checks = check(&crate::day01::Day01, &entries[0], input);
+
+        assert_eq!(checks[0].status, Status::Correct);
+        assert_eq!(checks[1].status, Status::Missing {
+            found: "142".to_string()
+        });
+
+        let checks = check(&crate::day01::Day01, &entries[1], input);
+        assert!(checks.iter().all(|check| check.status.is_failure()));
+
+        let checks = check_known(&crate::day01::Day01, &entries[0], input);
+        assert_eq!(checks.len(), 1);
+    }
+
+    #[test]
+    fn test_read_examples() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+        let examples = read_examples(&dir).unwrap();
+        assert!((1..=25).all(|day| examples.iter().any(|example| example.day == day)));
+        assert!(examples
+            .iter()
+            .all(|example| example.part_1.is_some() || example.part_2.is_some()));
+    }
+}
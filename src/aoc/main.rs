@@ -0,0 +1,865 @@
+use std::{
+    fs,
+    io::{self, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use aoc_2023::{
+    answers::{self, Status},
+    bench::{self, Measurement},
+    day01::{Decoder, DigitVocabulary, Token},
+    day02::{likelihood, Cubes, Day02, Games},
+    generate,
+    season::{self, Report},
+    stream::{self, StreamError},
+    viz::{self, Ansi, ImageFormat, Images, Sink, VizError},
+    Answers, Part, Solution,
+};
+use bstr::ByteSlice;
+use clap::{Parser, Subcommand, ValueEnum};
+
+/// Advent of Code 2023 solutions
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Write a Chrome trace of the parse and solve stages to this file, needs
+    /// the `trace` feature
+    #[arg(long, global = true)]
+    trace: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, a range of days or the whole calendar
+    Run {
+        /// `all`, a single day like `5` or an inclusive range like `3-7`
+        #[arg(default_value = "all", value_parser = parse_days)]
+        days: RangeInclusive<u8>,
+        /// Only run this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Input file, `-` reads stdin; only valid for a single day
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Also report the heap allocations of each stage, needs the
+        /// `alloc-stats` feature
+        #[arg(long)]
+        alloc_stats: bool,
+        /// Solve while reading the input instead of loading it first, for
+        /// inputs larger than memory; only days 1, 2, 4, 7, 9, 12, 15 and 18
+        #[arg(long, conflicts_with = "alloc_stats")]
+        stream: bool,
+        /// Print the digits found in each line and the value they make
+        /// instead of the sums; only day 1
+        #[arg(long, conflicts_with_all = ["alloc_stats", "stream"])]
+        explain: bool,
+        /// File of the words and their values part 2 of `--explain` finds
+        /// instead of the English ones, like `uno 1` on each line
+        #[arg(long, requires = "explain")]
+        vocabulary: Option<PathBuf>,
+        /// Print which games are possible with the cubes in this bag, like
+        /// `12 red, 13 green, 14 blue`, and the fewest cubes each game needs;
+        /// only day 2
+        #[arg(long, conflicts_with_all = ["part", "alloc_stats", "stream", "explain"])]
+        bag: Option<String>,
+        /// Print the bag with at most these cubes, like `20 red, 20 green,
+        /// 20 blue`, that makes the games the most likely and how likely
+        /// each game is with it; only day 2
+        #[arg(long, conflicts_with_all = ["part", "alloc_stats", "stream", "explain", "bag"])]
+        likeliest_bag: Option<String>,
+    },
+    /// Time parsing and both parts of each day, build with `--release`
+    Bench {
+        /// `all`, a single day like `5` or an inclusive range like `3-7`
+        #[arg(default_value = "all", value_parser = parse_days)]
+        days: RangeInclusive<u8>,
+        /// Input file, `-` reads stdin; only valid for a single day
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+        /// Write the results to this file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Results of an earlier run, as JSON or CSV, to compare against
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Slowdown of the median in percent above which a stage counts as a
+        /// regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+        /// Minimum time in milliseconds to measure each stage
+        #[arg(long, default_value_t = 500)]
+        min_time: u64,
+        /// Minimum number of measured runs of each stage
+        #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+        min_runs: u32,
+    },
+    /// Solve every day once and report how the total time splits up
+    Season {
+        /// `all`, a single day like `5` or an inclusive range like `3-7`
+        #[arg(default_value = "all", value_parser = parse_days)]
+        days: RangeInclusive<u8>,
+        /// Solve the days in parallel on all cores
+        #[arg(long)]
+        parallel: bool,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Check every registered input against its known answers
+    Verify {
+        /// `all`, a single day like `5` or an inclusive range like `3-7`
+        #[arg(default_value = "all", value_parser = parse_days)]
+        days: RangeInclusive<u8>,
+        /// Registry of inputs and their answers
+        #[arg(long, default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+    /// Write a random input for a day, for stress tests and scaling
+    /// benchmarks
+    Generate {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// What the size counts depends on the day, defaults to the size of
+        /// the real inputs
+        #[arg(long)]
+        size: Option<usize>,
+        /// The same seed and size always give the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Write the input to this file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Draw how a day on a map is solved, as images or an animation in the
+    /// terminal; only days 10, 14, 16, 17, 21 and 23
+    Viz {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Input file, `-` reads stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = VizFormat::Ansi)]
+        format: VizFormat,
+        /// Directory for the image files, needed for `ppm` and `png`
+        #[arg(long, required_if_eq_any = [("format", "ppm"), ("format", "png")])]
+        output: Option<PathBuf>,
+        /// Pixels per tile in the images
+        #[arg(
+            long,
+            default_value_t = 4,
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+        )]
+        scale: usize,
+        /// Milliseconds between the frames of the animation
+        #[arg(long, default_value_t = 50)]
+        delay: u64,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum VizFormat {
+    /// Animation in a terminal with 24-bit colour
+    Ansi,
+    Ppm,
+    /// Needs the `png` feature
+    Png,
+}
+
+/// Settings of the `bench` command.
+struct BenchArgs {
+    format: Format,
+    output: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    options: bench::Options,
+}
+
+fn parse_days(s: &str) -> Result<RangeInclusive<u8>, String> {
+    let day = |s: &str| match s.trim().parse::<u8>() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("`{s}` is not a day between 1 and 25")),
+    };
+
+    match s.split_once('-') {
+        _ if s == "all" => Ok(1..=25),
+        Some((start, end)) => {
+            let (start, end) = (day(start)?, day(end)?);
+            if start > end {
+                return Err(format!("`{s}` is an empty range"));
+            }
+            Ok(start..=end)
+        }
+        None => day(s).map(|day| day..=day),
+    }
+}
+
+/// Reads the input of `day` from `input` or the embedded one.
+fn read_input(day: u8, input: Option<&Path>) -> Result<Vec<u8>, String> {
+    let puzzle = aoc_2023::puzzle(day).unwrap();
+    if input.is_none() && puzzle.input().is_none() {
+        return Err(missing_input(day));
+    }
+    aoc_2023::load_input(input.map(Path::as_os_str), puzzle.input())
+        .map_err(|err| format!("failed to read input for day {day}: {err}"))
+}
+
+fn missing_input(day: u8) -> String {
+    format!("no input for day {day}, pass one with `--input` or enable the `embed-input` feature")
+}
+
+fn check_single_day(days: &RangeInclusive<u8>, input: Option<&Path>) -> Result<(), String> {
+    if input.is_some() && days.start() != days.end() {
+        return Err("`--input` can only be used when running a single day".to_string());
+    }
+    Ok(())
+}
+
+fn run(
+    days: RangeInclusive<u8>,
+    part: Option<Part>,
+    input: Option<PathBuf>,
+    alloc_stats: bool,
+    stream: bool,
+) -> Result<(), String> {
+    check_single_day(&days, input.as_deref())?;
+    if alloc_stats && !cfg!(feature = "alloc-stats") {
+        return Err("`--alloc-stats` needs a build with `--features alloc-stats`".to_string());
+    }
+    if let Some(day) = days
+        .clone()
+        .find(|day| stream && !stream::DAYS.contains(day))
+    {
+        return Err(format!("day {day} can't be solved with `--stream`"));
+    }
+
+    for day in days {
+        if stream {
+            let mut answers = stream_answers(day, input.as_deref())?;
+            // Both parts are solved in the same pass over the input.
+            match part {
+                Some(Part::One) => answers.part_2 = None,
+                Some(Part::Two) => answers.part_1 = None,
+                None => (),
+            }
+            print_answers(day, answers);
+            continue;
+        }
+
+        let input = read_input(day, input.as_deref())?;
+        let answers = aoc_2023::puzzle(day)
+            .unwrap()
+            .run(&input, part)
+            .map_err(|err| err.report(&input).to_string())?;
+        print_answers(day, answers);
+        #[cfg(feature = "alloc-stats")]
+        if alloc_stats {
+            print_alloc_stats(day, &input, part)?;
+        }
+    }
+    Ok(())
+}
+
+fn print_answers(day: u8, answers: Answers) {
+    println!("Day {day}");
+    if let Some(answer) = answers.part_1 {
+        println!("  Part 1: {answer}");
+    }
+    if let Some(answer) = answers.part_2 {
+        println!("  Part 2: {answer}");
+    }
+}
+
+/// Prints how each line of the day 1 input is decoded, see `--explain`.
+fn explain_day_1(
+    days: RangeInclusive<u8>,
+    part: Option<Part>,
+    input: Option<&Path>,
+    vocabulary: Option<&Path>,
+) -> Result<(), String> {
+    if days != (1..=1) {
+        return Err("`--explain` only works for day 1".to_string());
+    }
+    let input = read_input(1, input)?;
+    let vocabulary = match vocabulary {
+        Some(path) => {
+            let text = fs::read(path)
+                .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+            DigitVocabulary::parse(&text).map_err(|err| err.report(&text).to_string())?
+        }
+        None => DigitVocabulary::english(),
+    };
+    let decoders = [
+        (1, Decoder::new(DigitVocabulary::digits())),
+        (2, Decoder::new(vocabulary)),
+    ];
+    let decoders = match part {
+        Some(Part::One) => &decoders[..1],
+        Some(Part::Two) => &decoders[1..],
+        None => &decoders[..],
+    };
+    let lines = decoders
+        .iter()
+        .map(|(_, decoder)| decoder.diagnose(&input))
+        .collect::<Vec<_>>();
+
+    let token = |token: &Token| format!("{} {}..{}", token.word.as_bstr(), token.start, token.end);
+    println!(
+        "{:>5}  {:>4}  {:16}  {:16}  {:>6}",
+        "line", "part", "first", "last", "value"
+    );
+    let mut sums = vec![0; decoders.len()];
+    let mut missing = 0;
+    for i in 0..lines[0].len() {
+        // Counted once even if neither part finds a digit.
+        let mut has_digit = true;
+        for (((part, _), lines), sum) in decoders.iter().zip(&lines).zip(&mut sums) {
+            match &lines[i] {
+                Ok(decoded) => {
+                    *sum += decoded.value;
+                    println!(
+                        "{:>5}  {part:>4}  {:16}  {:16}  {:>6}",
+                        i + 1,
+                        token(&decoded.first),
+                        token(&decoded.last),
+                        decoded.value
+                    );
+                }
+                Err(err) => {
+                    has_digit = false;
+                    println!(
+                        "{:>5}  {part:>4}  expected {}, found {}",
+                        i + 1,
+                        err.expected,
+                        err.found
+                    );
+                }
+            }
+        }
+        if !has_digit {
+            missing += 1;
+        }
+    }
+    for ((part, _), sum) in decoders.iter().zip(sums) {
+        println!("{:>5}  {part:>4}  {sum:>42}", "sum");
+    }
+    if missing > 0 {
+        eprintln!("warning: {missing} lines have no digit, their value is 0");
+    }
+    Ok(())
+}
+
+/// The games of the day 2 input and `bag` parsed along with them, for the
+/// `flag` that passed the bag.
+fn day_2_games(
+    days: RangeInclusive<u8>,
+    input: Option<&Path>,
+    flag: &str,
+    bag: &str,
+) -> Result<(Games, Cubes), String> {
+    if days != (2..=2) {
+        return Err(format!("`{flag}` only works for day 2"));
+    }
+    let input = read_input(2, input)?;
+    let games = Day02::parse(&input).map_err(|err| err.report(&input).to_string())?;
+    let bag = games
+        .bag(bag.as_bytes())
+        .map_err(|err| format!("invalid bag: {}", err.report(bag.as_bytes())))?;
+    Ok((games, bag))
+}
+
+/// Prints the games of the day 2 input that are possible with `bag`, see
+/// `--bag`.
+fn possible_games_day_2(
+    days: RangeInclusive<u8>,
+    input: Option<&Path>,
+    bag: &str,
+) -> Result<(), String> {
+    let (games, bag) = day_2_games(days, input, "--bag", bag)?;
+
+    let palette = games.palette();
+    println!("{:>5}  {:8}  fewest cubes", "game", "possible");
+    for game in games.games() {
+        let possible = if game.is_possible(&bag) { "yes" } else { "no" };
+        let minimal = palette.format(game.minimal_bag());
+        println!("{:>5}  {possible:8}  {minimal}", game.id());
+    }
+    let possible = games
+        .possible(&bag)
+        .map(|game| u64::from(game.id()))
+        .collect::<Vec<_>>();
+    println!(
+        "{} of {} games are possible with {}, their ids add up to {}",
+        possible.len(),
+        games.games().len(),
+        palette.format(&bag),
+        possible.iter().sum::<u64>()
+    );
+    Ok(())
+}
+
+/// Prints the bag with at most `max` cubes that best explains the games of
+/// the day 2 input, see `--likeliest-bag`.
+fn likeliest_bag_day_2(
+    days: RangeInclusive<u8>,
+    input: Option<&Path>,
+    max: &str,
+) -> Result<(), String> {
+    let (games, max) = day_2_games(days, input, "--likeliest-bag", max)?;
+    let palette = games.palette();
+    let estimate = likelihood::likeliest_bag(&games, &likelihood::Bounds::up_to(max))
+        .ok_or("some game is impossible with every bag within the bounds")?;
+
+    println!(
+        "{:>5}  {:>12}  {:>14}",
+        "game", "probability", "ln probability"
+    );
+    for game in &estimate.games {
+        println!(
+            "{:>5}  {:>12.6e}  {:>14.4}",
+            game.id,
+            game.probability(),
+            game.ln_probability
+        );
+    }
+    println!(
+        "The likeliest bag is {}, with a ln likelihood of {:.4}",
+        palette.format(&estimate.bag),
+        estimate.ln_likelihood
+    );
+    Ok(())
+}
+
+/// Solves the day while reading its input, see [`read_input`] for where the
+/// input comes from.
+fn stream_answers(day: u8, input: Option<&Path>) -> Result<Answers, String> {
+    let read_error = |err| format!("failed to read input for day {day}: {err}");
+    let answers = match (input, aoc_2023::puzzle(day).unwrap().input()) {
+        (Some(path), _) if path != Path::new("-") => {
+            let file = fs::File::open(path).map_err(read_error)?;
+            stream::solve(day, io::BufReader::new(file))
+        }
+        (None, Some(embedded)) => stream::solve(day, embedded),
+        (None, None) => return Err(missing_input(day)),
+        _ => stream::solve(day, io::stdin().lock()),
+    };
+    // `run` only streams the days that support it.
+    answers.unwrap().map_err(|err| match err {
+        StreamError::Io(err) => read_error(err),
+        StreamError::Parse(err) => err.to_string(),
+    })
+}
+
+/// Solves the day again, counting the allocations of each stage.
+#[cfg(feature = "alloc-stats")]
+fn print_alloc_stats(day: u8, input: &[u8], part: Option<Part>) -> Result<(), String> {
+    use bench::Stage;
+
+    let stats = aoc_2023::puzzle(day)
+        .unwrap()
+        .alloc_stats(input)
+        .map_err(|err| err.report(input).to_string())?;
+    for (stage, stats) in stats {
+        let skipped = match stage {
+            Stage::Parse => false,
+            Stage::Part1 => part == Some(Part::Two),
+            Stage::Part2 => part == Some(Part::One),
+        };
+        if !skipped {
+            println!("  {stage:5}   {stats}");
+        }
+    }
+    Ok(())
+}
+
+fn run_bench(
+    days: RangeInclusive<u8>,
+    input: Option<PathBuf>,
+    args: BenchArgs,
+) -> Result<(), String> {
+    check_single_day(&days, input.as_deref())?;
+    let baseline = args
+        .baseline
+        .as_ref()
+        .map(|path| {
+            fs::read_to_string(path)
+                .and_then(|baseline| bench::read_measurements(&baseline))
+                .map_err(|err| format!("failed to read baseline {}: {err}", path.display()))
+        })
+        .transpose()?;
+
+    let mut measurements = Vec::new();
+    for day in days {
+        let input = read_input(day, input.as_deref())?;
+        measurements.extend(
+            aoc_2023::puzzle(day)
+                .unwrap()
+                .bench(&input, &args.options)
+                .map_err(|err| err.report(&input).to_string())?,
+        );
+    }
+
+    let written = match &args.output {
+        Some(path) => fs::File::create(path).and_then(|file| {
+            write_measurements(io::BufWriter::new(file), args.format, &measurements)
+        }),
+        None => write_measurements(io::stdout().lock(), args.format, &measurements),
+    };
+    written.map_err(|err| format!("failed to write results: {err}"))?;
+
+    let Some(baseline) = baseline else {
+        return Ok(());
+    };
+    let threshold = args.threshold / 100.0;
+    let mut regressions = 0;
+    for comparison in bench::compare(&baseline, &measurements) {
+        let regressed = comparison.is_regression(threshold);
+        regressions += usize::from(regressed);
+        eprintln!(
+            "day {:2} {:5}  {:>10.2?} -> {:>10.2?}  {:+6.1}%{}",
+            comparison.day,
+            comparison.stage,
+            Duration::from_nanos(comparison.baseline_ns),
+            Duration::from_nanos(comparison.current_ns),
+            comparison.change() * 100.0,
+            if regressed { "  regression" } else { "" }
+        );
+    }
+    if regressions > 0 {
+        return Err(format!(
+            "{regressions} stages are more than {}% slower than the baseline",
+            args.threshold
+        ));
+    }
+    Ok(())
+}
+
+fn run_season(days: RangeInclusive<u8>, parallel: bool, format: Format) -> Result<(), String> {
+    let puzzles = days
+        .map(|day| Ok((aoc_2023::puzzle(day).unwrap(), read_input(day, None)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+    let report = season::run(&puzzles, parallel).map_err(|err| {
+        let (_, input) = puzzles.iter().find(|(p, _)| p.day() == err.day).unwrap();
+        err.report(input).to_string()
+    })?;
+    write_report(io::stdout().lock(), format, &report)
+        .map_err(|err| format!("failed to write results: {err}"))
+}
+
+fn write_report(mut writer: impl Write, format: Format, report: &Report) -> io::Result<()> {
+    match format {
+        Format::Json => season::write_json(&mut writer, report)?,
+        Format::Csv => season::write_csv(&mut writer, report)?,
+        Format::Table => {
+            let ns = Duration::from_nanos;
+            writeln!(
+                writer,
+                "{:>5}  {:>10}  {:>10}  {:>10}  {:>10}  {:>6}",
+                "day", "parse", "part 1", "part 2", "total", "share"
+            )?;
+            for d in &report.days {
+                let part_2 = d
+                    .timing
+                    .part2_ns
+                    .map_or("-".to_string(), |t| format!("{:.2?}", ns(t)));
+                writeln!(
+                    writer,
+                    "{:>5}  {:>10.2?}  {:>10.2?}  {:>10}  {:>10.2?}  {:>5.1}%",
+                    d.day,
+                    ns(d.timing.parse_ns),
+                    ns(d.timing.part1_ns),
+                    part_2,
+                    ns(d.total_ns),
+                    d.share * 100.0
+                )?;
+            }
+            writeln!(
+                writer,
+                "{:>5}  {:>46.2?}  {:>5.1}%",
+                "total",
+                ns(report.total_ns),
+                100.0
+            )?;
+            writeln!(
+                writer,
+                "wall clock {:.2?}{}",
+                ns(report.wall_ns),
+                if report.parallel { " (parallel)" } else { "" }
+            )?;
+            if let Some(slowest) = report.slowest() {
+                writeln!(
+                    writer,
+                    "day {} takes {:.1}% of the total",
+                    slowest.day,
+                    slowest.share * 100.0
+                )?;
+            }
+        }
+    }
+    writer.flush()
+}
+
+fn verify(days: RangeInclusive<u8>, registry: &Path) -> Result<(), String> {
+    let entries = fs::read_to_string(registry)
+        .and_then(|s| answers::parse_registry(&s, registry.parent().unwrap_or(Path::new(""))))
+        .map_err(|err| format!("failed to read {}: {err}", registry.display()))?;
+
+    println!(
+        "{:>3}  {:12}  {:4}  {:>10}  status",
+        "day", "input", "part", "time"
+    );
+    let (mut failures, mut missing) = (0, 0);
+    for day in days {
+        let puzzle = aoc_2023::puzzle(day).unwrap();
+        let mut day_entries = entries.iter().filter(|entry| entry.day == day).peekable();
+        if day_entries.peek().is_none() {
+            missing += 1;
+            println!(
+                "{day:>3}  {:12}  {:4}  {:>10}  no inputs registered",
+                "-", "-", "-"
+            );
+        }
+
+        for entry in day_entries {
+            let input = match aoc_2023::load_input(Some(entry.input.as_os_str()), None) {
+                Ok(input) => input,
+                Err(err) => {
+                    failures += 1;
+                    println!(
+                        "{day:>3}  {:12}  {:4}  {:>10}  failed to read {}: {err}",
+                        entry.input_id,
+                        "-",
+                        "-",
+                        entry.input.display()
+                    );
+                    continue;
+                }
+            };
+            for check in answers::check(puzzle, entry, &input) {
+                failures += usize::from(check.status.is_failure());
+                missing += usize::from(matches!(check.status, Status::Missing { .. }));
+                println!(
+                    "{day:>3}  {:12}  {:4}  {:>10.2?}  {}",
+                    entry.input_id,
+                    if check.part == Part::One { 1 } else { 2 },
+                    check.time,
+                    check.status
+                );
+            }
+        }
+    }
+
+    if missing > 0 {
+        eprintln!("{missing} answers are missing from {}", registry.display());
+    }
+    if failures > 0 {
+        return Err(format!("{failures} checks failed"));
+    }
+    Ok(())
+}
+
+fn write_measurements(
+    mut writer: impl Write,
+    format: Format,
+    measurements: &[Measurement],
+) -> io::Result<()> {
+    match format {
+        Format::Json => bench::write_json(&mut writer, measurements)?,
+        Format::Csv => bench::write_csv(&mut writer, measurements)?,
+        Format::Table => {
+            writeln!(
+                writer,
+                "{:>3}  {:5}  {:>7}  {:>10}  {:>10}  {:>10}",
+                "day", "stage", "runs", "median", "min", "mean"
+            )?;
+            for m in measurements {
+                writeln!(
+                    writer,
+                    "{:>3}  {:5}  {:>7}  {:>10.2?}  {:>10.2?}  {:>10.2?}",
+                    m.day,
+                    m.stage,
+                    m.runs,
+                    Duration::from_nanos(m.median_ns),
+                    Duration::from_nanos(m.min_ns),
+                    Duration::from_nanos(m.mean_ns)
+                )?;
+            }
+        }
+    }
+    writer.flush()
+}
+
+fn run_generate(
+    day: u8,
+    size: Option<usize>,
+    seed: u64,
+    output: Option<&Path>,
+) -> Result<(), String> {
+    let generator = generate::generator(day).unwrap();
+    let input = generator
+        .generate(size.unwrap_or(generator.default_size), seed)
+        .map_err(|err| err.to_string())?;
+    match output {
+        Some(path) => fs::write(path, input + "\n")
+            .map_err(|err| format!("failed to write {}: {err}", path.display())),
+        None => {
+            println!("{input}");
+            Ok(())
+        }
+    }
+}
+
+fn run_viz(
+    day: u8,
+    input: Option<&Path>,
+    format: VizFormat,
+    output: Option<&Path>,
+    scale: usize,
+    delay: Duration,
+) -> Result<(), String> {
+    if !viz::DAYS.contains(&day) {
+        return Err(format!("day {day} has no visualization"));
+    }
+    let image_format = match format {
+        VizFormat::Ansi => None,
+        VizFormat::Ppm => Some(ImageFormat::Ppm),
+        #[cfg(feature = "png")]
+        VizFormat::Png => Some(ImageFormat::Png),
+        #[cfg(not(feature = "png"))]
+        VizFormat::Png => {
+            return Err("`--format png` needs a build with `--features png`".to_string())
+        }
+    };
+    let input = read_input(day, input)?;
+
+    let mut sink: Box<dyn Sink> = match (image_format, output) {
+        (Some(format), Some(dir)) => Box::new(
+            Images::new(dir, format, scale)
+                .map_err(|err| format!("failed to create {}: {err}", dir.display()))?,
+        ),
+        // Clap requires `--output` with the image formats.
+        _ => Box::new(Ansi::new(io::stdout().lock(), delay)),
+    };
+    viz::visualize(day, &input, sink.as_mut())
+        .unwrap()
+        .map_err(|err| match err {
+            VizError::Io(err) => format!("failed to write frame: {err}"),
+            VizError::Parse(err) => err.report(&input).to_string(),
+        })
+}
+
+/// Records spans to a Chrome trace-event file until the guard is dropped.
+#[cfg(feature = "trace")]
+fn start_trace(path: &Path) -> tracing_chrome::FlushGuard {
+    use tracing_subscriber::prelude::*;
+
+    let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+        .file(path)
+        .include_args(true)
+        .build();
+    tracing_subscriber::registry().with(layer).init();
+    guard
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if cli.trace.is_some() && !cfg!(feature = "trace") {
+        eprintln!("error: `--trace` needs a build with `--features trace`");
+        return ExitCode::FAILURE;
+    }
+    #[cfg(feature = "trace")]
+    let _trace = cli.trace.as_deref().map(start_trace);
+
+    let result = match cli.command {
+        Command::Run {
+            days,
+            part,
+            input,
+            alloc_stats,
+            stream,
+            explain,
+            vocabulary,
+            bag,
+            likeliest_bag,
+        } => {
+            let part = part.map(|part| if part == 1 { Part::One } else { Part::Two });
+            if let Some(bag) = bag {
+                possible_games_day_2(days, input.as_deref(), &bag)
+            } else if let Some(max) = likeliest_bag {
+                likeliest_bag_day_2(days, input.as_deref(), &max)
+            } else if explain {
+                explain_day_1(days, part, input.as_deref(), vocabulary.as_deref())
+            } else {
+                run(days, part, input, alloc_stats, stream)
+            }
+        }
+        Command::Bench {
+            days,
+            input,
+            format,
+            output,
+            baseline,
+            threshold,
+            min_time,
+            min_runs,
+        } => {
+            let args = BenchArgs {
+                format,
+                output,
+                baseline,
+                threshold,
+                options: bench::Options {
+                    min_time: Duration::from_millis(min_time),
+                    min_runs,
+                },
+            };
+            run_bench(days, input, args)
+        }
+        Command::Season {
+            days,
+            parallel,
+            format,
+        } => run_season(days, parallel, format),
+        Command::Verify { days, answers } => verify(days, &answers),
+        Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        } => run_generate(day, size, seed, output.as_deref()),
+        Command::Viz {
+            day,
+            input,
+            format,
+            output,
+            scale,
+            delay,
+        } => run_viz(
+            day,
+            input.as_deref(),
+            format,
+            output.as_deref(),
+            scale,
+            Duration::from_millis(delay),
+        ),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
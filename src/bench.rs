@@ -0,0 +1,273 @@
+//! Benchmarks of the days that work on stable Rust.
+//!
+//! Parsing and both parts of a day are timed separately, see
+//! [`Puzzle::bench`](crate::Puzzle::bench). Results can be written as JSON or
+//! CSV and compared against a saved baseline of either format. Meaningful
+//! numbers need a `--release` build.
+
+use std::{
+    fmt,
+    hint::black_box,
+    io::{self, Write},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Columns of the CSV format, in order.
+pub const CSV_HEADER: &str = "day,stage,runs,min_ns,median_ns,mean_ns";
+
+/// How long to measure each stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Minimum total time of the measured runs.
+    pub min_time: Duration,
+    /// Minimum number of measured runs, even if they exceed `min_time`. There
+    /// is always at least one.
+    pub min_runs: u32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            min_time: Duration::from_millis(500),
+            min_runs: 5,
+        }
+    }
+}
+
+/// The step of solving a day that is timed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::Part1 => "part1",
+            Self::Part2 => "part2",
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Timings of one stage of a day, over `runs` runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub runs: u32,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+}
+
+/// Times `f` after a warm-up run, until both limits of `options` are reached.
+pub fn measure<R>(
+    day: u8,
+    stage: Stage,
+    options: &Options,
+    mut f: impl FnMut() -> R,
+) -> Measurement {
+    black_box(f());
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    let min_runs = options.min_runs.max(1) as usize;
+    while samples.len() < min_runs || start.elapsed() < options.min_time {
+        let run = Instant::now();
+        black_box(f());
+        samples.push(run.elapsed().as_nanos() as u64);
+    }
+    samples.sort_unstable();
+
+    Measurement {
+        day,
+        stage,
+        runs: samples.len() as u32,
+        min_ns: samples[0],
+        median_ns: samples[samples.len() / 2],
+        mean_ns: samples.iter().sum::<u64>() / samples.len() as u64,
+    }
+}
+
+pub fn write_json(writer: impl Write, measurements: &[Measurement]) -> io::Result<()> {
+    serde_json::to_writer_pretty(writer, measurements).map_err(io::Error::from)
+}
+
+pub fn write_csv(mut writer: impl Write, measurements: &[Measurement]) -> io::Result<()> {
+    writeln!(writer, "{CSV_HEADER}")?;
+    for m in measurements {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{}",
+            m.day, m.stage, m.runs, m.min_ns, m.median_ns, m.mean_ns
+        )?;
+    }
+    Ok(())
+}
+
+/// Reads measurements written by [`write_json`] or [`write_csv`].
+pub fn read_measurements(s: &str) -> io::Result<Vec<Measurement>> {
+    if s.trim_start().starts_with('[') {
+        return serde_json::from_str(s).map_err(io::Error::from);
+    }
+
+    let mut lines = s.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header.trim() == CSV_HEADER => (),
+        _ => return Err(invalid_data(1, "CSV header")),
+    }
+    lines
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let line_number = i + 1;
+            let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+            let [day, stage, runs, min_ns, median_ns, mean_ns] = fields[..] else {
+                return Err(invalid_data(line_number, "number of fields"));
+            };
+            Ok(Measurement {
+                day: parse_field(day, line_number, "day")?,
+                stage: [Stage::Parse, Stage::Part1, Stage::Part2]
+                    .into_iter()
+                    .find(|s| s.as_str() == stage)
+                    .ok_or_else(|| invalid_data(line_number, "stage"))?,
+                runs: parse_field(runs, line_number, "runs")?,
+                min_ns: parse_field(min_ns, line_number, "min_ns")?,
+                median_ns: parse_field(median_ns, line_number, "median_ns")?,
+                mean_ns: parse_field(mean_ns, line_number, "mean_ns")?,
+            })
+        })
+        .collect()
+}
+
+fn invalid_data(line: usize, what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {line}: invalid {what}"),
+    )
+}
+
+fn parse_field<T: FromStr>(field: &str, line: usize, what: &str) -> io::Result<T> {
+    field.parse().map_err(|_| invalid_data(line, what))
+}
+
+/// Median times of a stage in a baseline and the current run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comparison {
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Comparison {
+    /// Relative change of the median, `0.1` is 10% slower.
+    pub fn change(&self) -> f64 {
+        self.current_ns as f64 / self.baseline_ns.max(1) as f64 - 1.0
+    }
+
+    /// Whether the stage got slower by more than `threshold`, e.g. `0.1`.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+/// Pairs up the stages measured both in `baseline` and `current`.
+pub fn compare(baseline: &[Measurement], current: &[Measurement]) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|m| {
+            let base = baseline
+                .iter()
+                .find(|b| (b.day, b.stage) == (m.day, m.stage))?;
+            Some(Comparison {
+                day: m.day,
+                stage: m.stage,
+                baseline_ns: base.median_ns,
+                current_ns: m.median_ns,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(stage: Stage, median_ns: u64) -> Measurement {
+        Measurement {
+            day: 5,
+            stage,
+            runs: 3,
+            min_ns: median_ns - 1,
+            median_ns,
+            mean_ns: median_ns + 1,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let measurements = vec![
+            measurement(Stage::Parse, 100),
+            measurement(Stage::Part2, 2000),
+        ];
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &measurements).unwrap();
+        let mut json = Vec::new();
+        write_json(&mut json, &measurements).unwrap();
+
+        for written in [csv, json] {
+            let read = read_measurements(std::str::from_utf8(&written).unwrap()).unwrap();
+            assert_eq!(read, measurements);
+        }
+        assert!(read_measurements("day,stage\n").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = [
+            measurement(Stage::Parse, 100),
+            measurement(Stage::Part1, 100),
+        ];
+        let current = [
+            measurement(Stage::Part1, 125),
+            measurement(Stage::Part2, 100),
+        ];
+        let comparisons = compare(&baseline, &current);
+
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].stage, Stage::Part1);
+        assert!(comparisons[0].is_regression(0.2));
+        assert!(!comparisons[0].is_regression(0.3));
+    }
+
+    #[test]
+    fn test_measure() {
+        let options = Options {
+            min_time: Duration::ZERO,
+            min_runs: 3,
+        };
+        let mut calls = 0;
+        let m = measure(1, Stage::Parse, &options, || calls += 1);
+        assert_eq!((m.runs, calls), (3, 4));
+        assert!(m.min_ns <= m.median_ns);
+
+        let options = Options {
+            min_runs: 0,
+            ..options
+        };
+        assert_eq!(measure(1, Stage::Parse, &options, || ()).runs, 1);
+    }
+}
@@ -0,0 +1,553 @@
+//! [Day 1: Trebuchet?!](https://adventofcode.com/2023/day/1)
+
+use std::{io, sync::OnceLock};
+
+use aho_corasick::{AhoCorasick, AhoCorasickKind, MatchKind, PatternID};
+use bstr::ByteSlice;
+use itertools::Itertools;
+use smallvec::SmallVec;
+
+use crate::{
+    parse::Parser,
+    stream::{self, StreamError},
+    ParseError, Solution,
+};
+
+/// Sum of the calibration values formed by the first and last digit of each
+/// line.
+pub fn part_1(input: &[u8]) -> Result<u64, ParseError> {
+    Ok(calibration_sum(input))
+}
+
+/// Like [`part_1`], but digits may also be spelled out as `one` to `nine`,
+/// see [`DigitVocabulary::english`].
+pub fn part_2(input: &[u8]) -> Result<u64, ParseError> {
+    Ok(spelled_calibration_sum(input))
+}
+
+/// Solves both parts reading one line of `reader` at a time, see
+/// [`stream`](crate::stream).
+pub fn solve_stream(reader: impl io::BufRead) -> Result<(u64, u64), StreamError> {
+    let decoder = Decoder::english();
+    let (mut part_1, mut part_2) = (0, 0);
+    stream::for_each_record(Day01::DAY, reader, b'\n', |_, line| {
+        part_1 += u64::from(calibration_value(line));
+        part_2 += decoder.value(line);
+        Ok(())
+    })?;
+    Ok((part_1, part_2))
+}
+
+fn calibration_sum(input: &[u8]) -> u64 {
+    let mut sum = 0;
+    // The first and last digit of the current line.
+    let mut digits = None;
+    let mut visit = |byte: u8| {
+        if byte == b'\n' {
+            sum += digits
+                .take()
+                .map_or(0, |(first, last)| u64::from(first * 10 + last));
+        } else {
+            let digit = byte - b'0';
+            digits = Some((digits.map_or(digit, |(first, _)| first), digit));
+        }
+    };
+
+    // Visits the digits and line breaks in order, 8 bytes at a time.
+    let mut chunks = input.chunks_exact(8);
+    for chunk in &mut chunks {
+        let word = u64::from_le_bytes(chunk.try_into().unwrap());
+        let mut marked = digit_bytes(word) | zero_bytes(word ^ u64::from_ne_bytes([b'\n'; 8]));
+        while marked != 0 {
+            visit(chunk[marked.trailing_zeros() as usize / 8]);
+            marked &= marked - 1;
+        }
+    }
+    for &byte in chunks.remainder() {
+        if byte == b'\n' || byte.is_ascii_digit() {
+            visit(byte);
+        }
+    }
+    visit(b'\n');
+    sum
+}
+
+fn calibration_value(line: &[u8]) -> u32 {
+    let first = first_digit(line).unwrap_or(0);
+    let last = last_digit(line).unwrap_or(0);
+    u32::from(first) * 10 + u32::from(last)
+}
+
+/// Marks the bytes of `word` that are ASCII digits by setting their high bit,
+/// the rest of the result is 0. Looks at 8 bytes at once like the fallback
+/// of `memchr`.
+#[inline]
+fn digit_bytes(word: u64) -> u64 {
+    const ONES: u64 = u64::from_ne_bytes([1; 8]);
+    const HIGH: u64 = ONES * 0x80;
+    // Without their high bit the bytes are at most 0x7f, so adding at most
+    // 0x50 to each doesn't carry into the next one. The high bit of a sum is
+    // set if the byte was at least `0`, or above `9` respectively.
+    let low = word & !HIGH;
+    let at_least_0 = low + ONES * u64::from(0x80 - b'0');
+    let above_9 = low + ONES * u64::from(0x80 - b'9' - 1);
+    at_least_0 & !above_9 & !word & HIGH
+}
+
+/// Marks the zero bytes of `word` by setting their high bit, the rest of the
+/// result is 0.
+#[inline]
+fn zero_bytes(word: u64) -> u64 {
+    const LOW: u64 = u64::from_ne_bytes([0x7f; 8]);
+    // The high bit of a sum is set if the byte has any of its low bits set.
+    !(((word & LOW) + LOW) | word) & !LOW
+}
+
+/// Value of the first ASCII digit in `line`.
+fn first_digit(line: &[u8]) -> Option<u8> {
+    let mut chunks = line.chunks_exact(8);
+    for chunk in &mut chunks {
+        let digits = digit_bytes(u64::from_le_bytes(chunk.try_into().unwrap()));
+        if digits != 0 {
+            return Some(chunk[digits.trailing_zeros() as usize / 8] - b'0');
+        }
+    }
+    let rest = chunks.remainder();
+    rest.iter().find(|c| c.is_ascii_digit()).map(|c| c - b'0')
+}
+
+/// Value of the last ASCII digit in `line`.
+fn last_digit(line: &[u8]) -> Option<u8> {
+    let mut chunks = line.rchunks_exact(8);
+    for chunk in &mut chunks {
+        let digits = digit_bytes(u64::from_le_bytes(chunk.try_into().unwrap()));
+        if digits != 0 {
+            return Some(chunk[7 - digits.leading_zeros() as usize / 8] - b'0');
+        }
+    }
+    let rest = chunks.remainder();
+    rest.iter()
+        .rev()
+        .find(|c| c.is_ascii_digit())
+        .map(|c| c - b'0')
+}
+
+fn spelled_calibration_sum(input: &[u8]) -> u64 {
+    Decoder::english().sum(input)
+}
+
+/// Words that stand for values in a calibration document.
+///
+/// In a vocabulary file each line holds a word and its value separated by
+/// whitespace, like `uno 1`. The word is everything before the last space,
+/// so it may contain spaces itself. Blank lines and lines starting with `#`
+/// are skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DigitVocabulary {
+    words: Vec<(Vec<u8>, u32)>,
+}
+
+impl DigitVocabulary {
+    /// The digits `0` to `9`, which [`part_1`] looks for.
+    pub fn digits() -> Self {
+        let mut vocabulary = Self::default();
+        for value in 0..10 {
+            vocabulary.insert(value.to_string(), value);
+        }
+        vocabulary
+    }
+
+    /// The digits `1` to `9` and the words `one` to `nine`, used by
+    /// [`part_2`].
+    pub fn english() -> Self {
+        let names = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        let mut vocabulary = Self::default();
+        for (value, name) in (1..).zip(names) {
+            vocabulary.insert(value.to_string(), value);
+            vocabulary.insert(name, value);
+        }
+        vocabulary
+    }
+
+    /// Reads a vocabulary file.
+    pub fn parse(text: &[u8]) -> Result<Self, ParseError> {
+        let parser = Parser::new(Day01::DAY, text);
+        let mut vocabulary = Self::default();
+        for line in text.lines() {
+            let line = line.trim_end();
+            if line.trim_start().is_empty() || line.starts_with(b"#") {
+                continue;
+            }
+            let Some(i) = line.rfind_byteset(b" \t") else {
+                return Err(parser.error(line, "a word and its value"));
+            };
+            let word = line[..i].trim();
+            let value = parser.number(&line[i + 1..])?;
+            if word.is_empty() {
+                return Err(parser.error(line, "a word and its value"));
+            }
+            if vocabulary.value(word).is_some() {
+                return Err(parser.error(word, "a word not listed before"));
+            }
+            vocabulary.insert(word, value);
+        }
+        Ok(vocabulary)
+    }
+
+    /// Adds `word` standing for `value`, replacing its earlier value.
+    ///
+    /// # Panics
+    ///
+    /// If `word` is empty or holds a line break.
+    pub fn insert(&mut self, word: impl Into<Vec<u8>>, value: u32) {
+        let word = word.into();
+        assert!(
+            !word.is_empty() && !word.contains(&b'\n'),
+            "digit vocabulary words must be non-empty lines"
+        );
+        match self.words.iter_mut().find(|(w, _)| *w == word) {
+            Some((_, v)) => *v = value,
+            None => self.words.push((word, value)),
+        }
+    }
+
+    /// The value `word` stands for, if it's in the vocabulary.
+    pub fn value(&self, word: &[u8]) -> Option<u32> {
+        self.words.iter().find(|(w, _)| w == word).map(|&(_, v)| v)
+    }
+
+    /// The words with their values, in the order they were added.
+    pub fn words(&self) -> impl Iterator<Item = (&[u8], u32)> {
+        self.words.iter().map(|(w, v)| (&w[..], *v))
+    }
+}
+
+/// A word of the vocabulary found in a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub word: &'a [u8],
+    pub value: u32,
+    /// Byte offset of the word in the line.
+    pub start: usize,
+    /// Byte offset just past the word.
+    pub end: usize,
+}
+
+/// The tokens a line's calibration value is made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decoded<'a> {
+    /// The token starting first, the longest one if several do.
+    pub first: Token<'a>,
+    /// The token ending last, the longest one if several do.
+    pub last: Token<'a>,
+    /// Ten times the value of `first` plus the one of `last`.
+    pub value: u64,
+}
+
+/// Finds the words of a [`DigitVocabulary`] in the lines of a calibration
+/// document. Words may overlap, `twone` holds both `two` and `one`.
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    vocabulary: DigitVocabulary,
+    /// Finds the first token.
+    forward: AhoCorasick,
+    /// Finds the last token as the first one of the reversed line, made of
+    /// the reversed words.
+    backward: AhoCorasick,
+}
+
+impl Decoder {
+    /// # Panics
+    ///
+    /// If the vocabulary is too large to search for, which takes millions
+    /// of words.
+    pub fn new(vocabulary: DigitVocabulary) -> Self {
+        let mut builder = AhoCorasick::builder();
+        // Of the tokens starting first, the longest.
+        builder
+            .match_kind(MatchKind::LeftmostLongest)
+            .kind(Some(AhoCorasickKind::DFA));
+        let words = vocabulary.words.iter().map(|(word, _)| word);
+        let forward = builder.build(words.clone());
+        let backward = builder.build(words.map(|word| word.iter().rev().copied().collect_vec()));
+        Self {
+            vocabulary,
+            forward: forward.expect("digit vocabulary too large"),
+            backward: backward.expect("digit vocabulary too large"),
+        }
+    }
+
+    /// The decoder of [`DigitVocabulary::english`], built on first use.
+    pub fn english() -> &'static Self {
+        static ENGLISH: OnceLock<Decoder> = OnceLock::new();
+        ENGLISH.get_or_init(|| Self::new(DigitVocabulary::english()))
+    }
+
+    pub fn vocabulary(&self) -> &DigitVocabulary {
+        &self.vocabulary
+    }
+
+    /// The first and last token of `line`, `None` if it has none.
+    pub fn decode(&self, line: &[u8]) -> Option<Decoded<'_>> {
+        let first = self.forward.find(line)?;
+        let reversed = line.iter().rev().copied().collect::<SmallVec<[u8; 128]>>();
+        let last = self.backward.find(&reversed[..]).unwrap();
+
+        let token = |pattern: PatternID, start, end| {
+            let (word, value) = &self.vocabulary.words[pattern];
+            Token {
+                word,
+                value: *value,
+                start,
+                end,
+            }
+        };
+        let first = token(first.pattern(), first.start(), first.end());
+        let last = token(
+            last.pattern(),
+            line.len() - last.end(),
+            line.len() - last.start(),
+        );
+        Some(Decoded {
+            first,
+            last,
+            value: u64::from(first.value) * 10 + u64::from(last.value),
+        })
+    }
+
+    /// Calibration value of `line`, like in part 1 a line without any token
+    /// is worth nothing.
+    pub fn value(&self, line: &[u8]) -> u64 {
+        self.decode(line).map_or(0, |decoded| decoded.value)
+    }
+
+    /// Sum of the calibration values of all lines of `input`.
+    pub fn sum(&self, input: &[u8]) -> u64 {
+        input.lines().map(|line| self.value(line)).sum()
+    }
+
+    /// How each line of `input` is decoded, one entry per line. A line
+    /// without any token is an error, where [`Decoder::sum`] counts it as 0.
+    pub fn diagnose(&self, input: &[u8]) -> Vec<Result<Decoded<'_>, ParseError>> {
+        let parser = Parser::new(Day01::DAY, input);
+        input
+            .lines()
+            .map(|line| {
+                self.decode(line)
+                    .ok_or_else(|| parser.error(line, "a digit"))
+            })
+            .collect()
+    }
+}
+
+/// The [`Solution`] of this day.
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed<'a> = &'a [u8];
+    type Part1 = u64;
+    type Part2 = u64;
+
+    const DAY: u8 = 1;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-01/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        Ok(input)
+    }
+
+    fn part_1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        calibration_sum(input)
+    }
+
+    fn part_2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        spelled_calibration_sum(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE_01: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+    const EXAMPLE_02: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE_01.as_bytes()), Ok(142));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE_02.as_bytes()), Ok(281));
+    }
+
+    #[test]
+    fn test_vocabulary() {
+        let vocabulary = DigitVocabulary::parse(
+            b"# Spanish, with extra tokens\ncero 0\nuno\t1\n\ndiez 10\nveinte y uno 21\n",
+        )
+        .unwrap();
+        assert_eq!(vocabulary.value(b"cero"), Some(0));
+        assert_eq!(vocabulary.value(b"uno"), Some(1));
+        assert_eq!(vocabulary.value(b"veinte y uno"), Some(21));
+        assert_eq!(vocabulary.words().count(), 4);
+
+        let decoder = Decoder::new(vocabulary);
+        let decoded = decoder.decode(b"xdiezunoxcerox").unwrap();
+        assert_eq!(decoded.first.word, b"diez");
+        let cero = Token {
+            word: b"cero",
+            value: 0,
+            start: 9,
+            end: 13,
+        };
+        assert_eq!(decoded.last, cero);
+        assert_eq!(decoded.value, 100);
+        // `uno` ends the line too, but the longer token wins.
+        assert_eq!(decoder.value(b"veinte y uno"), 21 * 10 + 21);
+        assert_eq!(decoder.decode(b"one"), None);
+    }
+
+    #[test]
+    fn test_vocabulary_error() {
+        let err = DigitVocabulary::parse(b"uno 1\ndos\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a word and its value");
+        let err = DigitVocabulary::parse(b"uno 1\nuno 2").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = DigitVocabulary::parse(b"uno one").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let input = b"a1b2c\nnothing\n7pqrstsixteen";
+        let (digits, english) = (
+            Decoder::new(DigitVocabulary::digits()),
+            Decoder::new(DigitVocabulary::english()),
+        );
+        let lines = digits.diagnose(input);
+        assert_eq!(lines.len(), 3);
+        let first = lines[0].as_ref().unwrap();
+        assert_eq!((first.first.start, first.last.end, first.value), (1, 4, 12));
+        let err = lines[1].as_ref().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a digit");
+        let last = english.diagnose(input).pop().unwrap().unwrap();
+        assert_eq!((last.last.word, last.last.start), (&b"six"[..], 6));
+
+        // The lines add up to the answers of both parts.
+        let total = |decoder: &Decoder| {
+            decoder
+                .diagnose(input)
+                .iter()
+                .flatten()
+                .map(|d| d.value)
+                .sum()
+        };
+        assert_eq!(part_1(input), Ok(total(&digits)));
+        assert_eq!(part_2(input), Ok(total(&english)));
+    }
+
+    #[test]
+    fn test_overlapping_tokens() {
+        let decoder = Decoder::new(DigitVocabulary::english());
+        let decoded = decoder.decode(b"xtwone").unwrap();
+        assert_eq!(decoded.first.word, b"two");
+        assert_eq!(decoded.last.word, b"one");
+        assert_eq!(decoded.value, 21);
+    }
+
+    /// Looks for the digits one byte at a time.
+    fn calibration_value_naive(line: &[u8]) -> u64 {
+        let mut digits = None;
+        for &c in line {
+            if c.is_ascii_digit() {
+                let digit = u64::from(c - b'0');
+                digits = Some((digits.map_or(digit, |(first, _)| first), digit));
+            }
+        }
+        digits.map_or(0, |(first, last)| first * 10 + last)
+    }
+
+    /// Tries every word at every position of `line`, as the start and end
+    /// offsets of the first and last token.
+    fn decode_naive(
+        vocabulary: &DigitVocabulary,
+        line: &[u8],
+    ) -> Option<((usize, usize), (usize, usize))> {
+        let tokens = (0..line.len())
+            .flat_map(|start| {
+                vocabulary
+                    .words()
+                    .filter(move |&(word, _)| line[start..].starts_with(word))
+                    .map(move |(word, _)| (start, start + word.len()))
+            })
+            .collect::<Vec<_>>();
+        let first = tokens
+            .iter()
+            .min_by_key(|&&(start, end)| (start, Reverse(end)))?;
+        let last = tokens
+            .iter()
+            .max_by_key(|&&(start, end)| (end, Reverse(start)))?;
+        Some((*first, *last))
+    }
+
+    /// Lines made of digits, bits of spelled digits and other bytes.
+    fn document() -> impl Strategy<Value = Vec<u8>> {
+        let piece = prop_oneof![
+            proptest::sample::select(vec![
+                "one", "two", "thr", "three", "four", "fiv", "five", "six", "seven", "eigh",
+                "eight", "nine", "ni", "ne", "twone", "oneight", "1", "7", "\n", "\r\n", "x",
+                "teen",
+            ])
+            .prop_map(|piece| piece.as_bytes().to_vec()),
+            any::<u8>().prop_map(|byte| vec![byte]),
+        ];
+        proptest::collection::vec(piece, 0..40).prop_map(|pieces| pieces.concat())
+    }
+
+    proptest! {
+        #[test]
+        fn test_calibration_sum(input in document()) {
+            let naive = input.lines().map(calibration_value_naive).sum::<u64>();
+            prop_assert_eq!(calibration_sum(&input), naive);
+            for line in input.lines() {
+                prop_assert_eq!(u64::from(calibration_value(line)), calibration_value_naive(line));
+            }
+        }
+
+        #[test]
+        fn test_decode(input in document()) {
+            let vocabulary = DigitVocabulary::parse(b"one 1\nnine 9\nteen 10\nsixteen 16\nsix 6\nx 0\n").unwrap();
+            for decoder in [Decoder::english(), &Decoder::new(vocabulary)] {
+                for line in input.lines() {
+                    let decoded = decoder.decode(line).map(|decoded| {
+                        let (first, last) = (decoded.first, decoded.last);
+                        prop_assert_eq!(first.word, &line[first.start..first.end]);
+                        prop_assert_eq!(last.word, &line[last.start..last.end]);
+                        Ok(((first.start, first.end), (last.start, last.end)))
+                    }).transpose()?;
+                    prop_assert_eq!(decoded, decode_naive(decoder.vocabulary(), line));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! [Day 2: Cube Conundrum](https://adventofcode.com/2023/day/2)
+
+pub mod likelihood;
+
+use std::{borrow::Cow, io};
+
+use bstr::ByteSlice;
+use itertools::Itertools;
+use smallvec::SmallVec;
+
+use crate::{
+    parse::Parser,
+    stream::{self, StreamError},
+    ParseError, Solution,
+};
+
+/// The colours the puzzle's bag and power are about.
+const PUZZLE_COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// Names of the colours of the cubes, numbered in the order the games show
+/// them first after the ones of the puzzle, red, green and blue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    names: Vec<String>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            names: PUZZLE_COLOURS.map(String::from).to_vec(),
+        }
+    }
+}
+
+impl Palette {
+    /// Number of the colour `name`, `None` if it isn't in the palette.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    pub fn name(&self, colour: usize) -> &str {
+        &self.names[colour]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Writes `cubes` like a handful in the input, `4 red, 2 green`.
+    pub fn format(&self, cubes: &Cubes) -> String {
+        cubes
+            .iter()
+            .map(|(colour, count)| format!("{count} {}", self.name(colour)))
+            .join(", ")
+    }
+
+    /// Number of the colour `name`, which is added if it's new. What was
+    /// expected instead if `name` isn't made of ASCII letters.
+    fn insert(&mut self, name: &[u8]) -> Result<usize, Cow<'static, str>> {
+        if let Some(colour) = self.names.iter().position(|n| n.as_bytes() == name) {
+            return Ok(colour);
+        }
+        if name.is_empty() || !name.iter().all(u8::is_ascii_alphabetic) {
+            return Err("a colour".into());
+        }
+        self.names.push(name.to_str().unwrap().to_string());
+        Ok(self.names.len() - 1)
+    }
+}
+
+/// How many cubes of each colour of a [`Palette`] there are, in a handful
+/// shown from the bag or in the bag itself.
+#[derive(Debug, Clone, Default)]
+pub struct Cubes {
+    /// Indexed by colour, there are none of the colours past the end.
+    counts: SmallVec<[u32; 4]>,
+}
+
+impl PartialEq for Cubes {
+    fn eq(&self, other: &Self) -> bool {
+        let colours = self.counts.len().max(other.counts.len());
+        (0..colours).all(|colour| self.count(colour) == other.count(colour))
+    }
+}
+
+impl Eq for Cubes {}
+
+impl Cubes {
+    pub fn count(&self, colour: usize) -> u32 {
+        self.counts.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, colour: usize, count: u32) {
+        if colour >= self.counts.len() {
+            self.counts.resize(colour + 1, 0);
+        }
+        self.counts[colour] = count;
+    }
+
+    /// The colours there are any cubes of, with their number.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, u32)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(colour, &count)| (colour, count))
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().map(|&count| u64::from(count)).sum()
+    }
+
+    /// Whether there are at least as many cubes of each colour as in `other`.
+    pub fn contains(&self, other: &Self) -> bool {
+        other
+            .counts
+            .iter()
+            .enumerate()
+            .all(|(colour, &count)| self.count(colour) >= count)
+    }
+
+    /// The fewest cubes that contain both `self` and `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other);
+        union
+    }
+
+    /// Adds cubes until there are at least as many of each colour as in
+    /// `other`.
+    pub fn extend(&mut self, other: &Self) {
+        if other.counts.len() > self.counts.len() {
+            self.counts.resize(other.counts.len(), 0);
+        }
+        for (count, &other) in self.counts.iter_mut().zip(&other.counts) {
+            *count = (*count).max(other);
+        }
+    }
+
+    /// Product of the red, green and blue cubes, other colours don't count.
+    /// `None` if that doesn't fit in a `u64`.
+    pub fn power(&self) -> Option<u64> {
+        (0..PUZZLE_COLOURS.len()).try_fold(1u64, |power, colour| {
+            power.checked_mul(self.count(colour).into())
+        })
+    }
+
+    /// Parses cubes written like `3 blue, 4 red`, adding new colours to
+    /// `palette`.
+    fn parse(parser: &Parser, input: &[u8], palette: &mut Palette) -> Result<Self, ParseError> {
+        let mut cubes = Self::default();
+        let mut shown = SmallVec::<[usize; 4]>::new();
+        for ins in input.split_str(", ") {
+            let (count, name) = parser.split_once(ins, " ")?;
+            let count = parser.number(count)?;
+            let colour = palette
+                .insert(name)
+                .map_err(|expected| parser.error(name, expected))?;
+            if shown.contains(&colour) {
+                return Err(parser.error(name, "a colour not shown yet in this handful"));
+            }
+            shown.push(colour);
+            cubes.set(colour, count);
+        }
+        Ok(cubes)
+    }
+}
+
+/// A game of revealing handfuls of cubes from the bag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    id: u32,
+    handfuls: Vec<Cubes>,
+    /// The fewest cubes of each colour that make the game possible.
+    minimal_bag: Cubes,
+}
+
+impl Game {
+    fn parse(parser: &Parser, line: &[u8], palette: &mut Palette) -> Result<Self, ParseError> {
+        let (id, handfuls) = parser.split_once(parser.strip_prefix(line, "Game ")?, ": ")?;
+        let id = parser.number(id)?;
+        let handfuls = handfuls
+            .split_str("; ")
+            .map(|handful| Cubes::parse(parser, handful, palette))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut minimal_bag = Cubes::default();
+        for handful in &handfuls {
+            minimal_bag.extend(handful);
+        }
+        Ok(Self {
+            id,
+            handfuls,
+            minimal_bag,
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The handfuls revealed in turn.
+    pub fn handfuls(&self) -> &[Cubes] {
+        &self.handfuls
+    }
+
+    /// Whether every handful of the game can be drawn from `bag`.
+    pub fn is_possible(&self, bag: &Cubes) -> bool {
+        bag.contains(&self.minimal_bag)
+    }
+
+    /// The fewest cubes of each colour that make the game possible.
+    pub fn minimal_bag(&self) -> &Cubes {
+        &self.minimal_bag
+    }
+}
+
+/// The games of an input and the colours they show.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Games {
+    palette: Palette,
+    games: Vec<Game>,
+}
+
+impl Games {
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    pub fn games(&self) -> &[Game] {
+        &self.games
+    }
+
+    /// Parses the contents of a bag written like a handful, e.g.
+    /// `12 red, 13 green, 14 blue`. Colours no game shows are left out, they
+    /// don't change which games are possible.
+    pub fn bag(&self, text: &[u8]) -> Result<Cubes, ParseError> {
+        let parser = Parser::new(Day02::DAY, text);
+        // The colours no game shows get numbers past the palette's.
+        let mut bag = Cubes::parse(&parser, text, &mut self.palette.clone())?;
+        bag.counts.truncate(self.palette.len());
+        Ok(bag)
+    }
+
+    /// The bag of part 1, with 12 red, 13 green and 14 blue cubes.
+    pub fn puzzle_bag() -> Cubes {
+        Cubes {
+            counts: SmallVec::from_slice(&[12, 13, 14]),
+        }
+    }
+
+    /// The games that are possible with `bag`.
+    pub fn possible<'a>(&'a self, bag: &'a Cubes) -> impl Iterator<Item = &'a Game> + 'a {
+        self.games.iter().filter(|game| game.is_possible(bag))
+    }
+}
+
+fn parse(input: &[u8]) -> Result<Games, ParseError> {
+    let parser = Parser::new(Day02::DAY, input);
+    let mut palette = Palette::default();
+    let games = input
+        .lines()
+        .map(|line| Game::parse(&parser, line, &mut palette))
+        .collect::<Result<_, _>>()?;
+    Ok(Games { palette, games })
+}
+
+/// Sum of the ids of the games possible with 12 red, 13 green and 14 blue
+/// cubes in the bag.
+pub fn part_1(input: &[u8]) -> Result<u64, ParseError> {
+    parse(input).map(|games| possible_games(&games))
+}
+
+/// Sum of the powers of the fewest cubes that make each game possible, `None`
+/// if that doesn't fit in a `u64`.
+pub fn part_2(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|games| minimum_power(&games))
+}
+
+/// Solves both parts reading one game of `reader` at a time, see
+/// [`stream`](crate::stream).
+pub fn solve_stream(reader: impl io::BufRead) -> Result<(u64, Option<u64>), StreamError> {
+    let (mut part_1, mut part_2) = (0, Some(0u64));
+    let (mut palette, bag) = (Palette::default(), Games::puzzle_bag());
+    stream::for_each_record(Day02::DAY, reader, b'\n', |parser, line| {
+        let game = Game::parse(parser, line, &mut palette)?;
+        if game.is_possible(&bag) {
+            part_1 += u64::from(game.id);
+        }
+        part_2 = part_2.and_then(|sum| sum.checked_add(game.minimal_bag().power()?));
+        Ok(())
+    })?;
+    Ok((part_1, part_2))
+}
+
+fn possible_games(games: &Games) -> u64 {
+    games
+        .possible(&Games::puzzle_bag())
+        .map(|game| u64::from(game.id))
+        .sum()
+}
+
+fn minimum_power(games: &Games) -> Option<u64> {
+    games
+        .games
+        .iter()
+        .try_fold(0u64, |sum, game| sum.checked_add(game.minimal_bag().power()?))
+}
+
+/// The [`Solution`] of this day.
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed<'a> = Games;
+    type Part1 = u64;
+    type Part2 = Option<u64>;
+
+    const DAY: u8 = 2;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-02/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(games: &Self::Parsed<'_>) -> Self::Part1 {
+        possible_games(games)
+    }
+
+    fn part_2(games: &Self::Parsed<'_>) -> Self::Part2 {
+        minimum_power(games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) const EXAMPLE: &[u8] = b"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(8));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(Some(2286)));
+    }
+
+    #[test]
+    fn test_power_overflow() {
+        let game = |id| format!("Game {id}: 65535 red, 65535 green, 65535 blue");
+        let games = (1..=70_000).map(game).join("\n");
+        assert_eq!(part_2(games.as_bytes()), Ok(None));
+        assert_eq!(solve_stream(games.as_bytes()).unwrap().1, None);
+
+        // The power of a single game doesn't fit either.
+        let games = b"Game 1: 4294967295 red, 4294967295 green, 2 blue";
+        assert_eq!(part_2(games), Ok(None));
+        assert_eq!(part_2(b"Game 1: 4294967295 red, 4294967295 green"), Ok(Some(0)));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = part_1(b"Game 1: 3 blue, 4 red\nGame 2: 1 blue; 2 gr3y").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 19));
+        assert_eq!(err.expected, "a colour");
+        assert_eq!(err.found, "`gr3y`");
+        let err = part_1(b"Game 1: 3 blue, 4 blue").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 19));
+    }
+
+    #[test]
+    fn test_colours() {
+        let games = parse(b"Game 1: 2 teal, 1 red; 5 teal\nGame 2: 1 grey; 3 teal").unwrap();
+        let palette = games.palette();
+        assert_eq!(palette.len(), 5);
+        let (teal, grey) = (
+            palette.index("teal").unwrap(),
+            palette.index("grey").unwrap(),
+        );
+        assert_eq!((teal, grey), (3, 4));
+
+        let minimal = games.games()[0].minimal_bag();
+        assert_eq!(palette.format(minimal), "1 red, 5 teal");
+        // A game without blue or green cubes has no power.
+        assert_eq!(minimal.power(), Some(0));
+        assert_eq!(part_1(b"Game 1: 1 grey\nGame 2: 4 red"), Ok(2));
+
+        let bag = games.bag(b"4 teal, 1 grey, 9 purple").unwrap();
+        assert_eq!(palette.format(&bag), "4 teal, 1 grey");
+        let ids = games.possible(&bag).map(Game::id).collect::<Vec<_>>();
+        assert_eq!(ids, [2]);
+        let bag = bag.union(minimal);
+        assert_eq!(games.possible(&bag).count(), 2);
+        assert!(bag.contains(minimal));
+        assert_eq!(bag.total(), 7);
+    }
+
+    #[test]
+    fn test_many_colours() {
+        // Colours `a`, `b`, … `zz`, … beyond red, green and blue.
+        let games = (0..1000)
+            .map(|i| {
+                let name = [b'a' + (i % 26) as u8].repeat(i / 26 + 1);
+                format!("Game {i}: 1 {}", name.to_str().unwrap())
+            })
+            .join("\n");
+        assert_eq!(parse(games.as_bytes()).unwrap().palette().len(), 1003);
+        assert!(solve_stream(games.as_bytes()).is_ok());
+    }
+}
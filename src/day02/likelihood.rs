@@ -0,0 +1,312 @@
+//! Which bags explain the games best, beyond whether they are possible.
+//!
+//! Each handful is drawn from the bag without replacement, and the cubes go
+//! back into the bag before the next one. A handful of `k` cubes of each
+//! colour `c` from a bag of `b` cubes of each colour is then drawn with the
+//! multivariate hypergeometric probability
+//!
+//! ```text
+//! C(b_red, k_red) · C(b_green, k_green) · … / C(b_total, k_total)
+//! ```
+//!
+//! and the handfuls of the games are independent. The probabilities get tiny
+//! quickly, so they are worked with as natural logarithms.
+
+use super::{Cubes, Games};
+
+/// The bags a search looks at, with `min` to `max` cubes of each colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Cubes,
+    /// The bags have none of the colours without a maximum.
+    pub max: Cubes,
+}
+
+impl Bounds {
+    /// The bags with at most `max` cubes of each colour.
+    pub fn up_to(max: Cubes) -> Self {
+        Self {
+            min: Cubes::default(),
+            max,
+        }
+    }
+}
+
+/// How likely a game is with some bag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameProbability {
+    pub id: u32,
+    /// Natural logarithm of the probability of drawing all the handfuls of
+    /// the game, negative infinity if the game is impossible.
+    pub ln_probability: f64,
+}
+
+impl GameProbability {
+    pub fn probability(&self) -> f64 {
+        self.ln_probability.exp()
+    }
+}
+
+/// The likeliest bag found by [`likeliest_bag`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub bag: Cubes,
+    /// Natural logarithm of the probability of all the games with the bag.
+    pub ln_likelihood: f64,
+    /// The probability of each game with the bag, in the order of the input.
+    pub games: Vec<GameProbability>,
+}
+
+/// Natural logarithms of `0!` up to some `n!`.
+struct LnFactorials(Vec<f64>);
+
+impl LnFactorials {
+    fn new(n: u64) -> Self {
+        let mut ln = Vec::with_capacity(n as usize + 1);
+        ln.push(0.0);
+        for i in 1..=n {
+            ln.push(ln[i as usize - 1] + (i as f64).ln());
+        }
+        Self(ln)
+    }
+
+    /// Natural logarithm of `n` choose `k`, negative infinity if `k > n`.
+    fn ln_choose(&self, n: u64, k: u64) -> f64 {
+        if k > n {
+            return f64::NEG_INFINITY;
+        }
+        let ln = &self.0;
+        ln[n as usize] - ln[k as usize] - ln[(n - k) as usize]
+    }
+
+    /// Natural logarithm of the probability of drawing `handful` from `bag`,
+    /// negative infinity if the bag doesn't hold it.
+    fn ln_probability(&self, bag: &Cubes, handful: &Cubes) -> f64 {
+        // Both terms would be negative infinity and their difference NaN.
+        if !bag.contains(handful) {
+            return f64::NEG_INFINITY;
+        }
+        let colours = handful
+            .iter()
+            .map(|(colour, count)| self.ln_choose(bag.count(colour).into(), count.into()))
+            .sum::<f64>();
+        colours - self.ln_choose(bag.total(), handful.total())
+    }
+}
+
+/// Probability of drawing `handful` from `bag`.
+pub fn handful_probability(bag: &Cubes, handful: &Cubes) -> f64 {
+    LnFactorials::new(bag.total())
+        .ln_probability(bag, handful)
+        .exp()
+}
+
+/// How likely each of the games is with `bag`, in the order of the input.
+pub fn probabilities(games: &Games, bag: &Cubes) -> Vec<GameProbability> {
+    let ln = LnFactorials::new(bag.total());
+    games
+        .games()
+        .iter()
+        .map(|game| GameProbability {
+            id: game.id(),
+            ln_probability: game
+                .handfuls()
+                .iter()
+                .map(|handful| ln.ln_probability(bag, handful))
+                .sum(),
+        })
+        .collect()
+}
+
+/// The bag within `bounds` that makes the games the most likely, `None` if
+/// some game is impossible with all of them. Of bags that are equally likely
+/// the one with the fewest cubes of the first colours wins.
+///
+/// The logarithm of the likelihood is a sum of a term for each colour and
+/// its number of cubes, minus a term for the total number of cubes. So for
+/// each total the best split among the colours is found colour by colour,
+/// without trying every bag.
+pub fn likeliest_bag(games: &Games, bounds: &Bounds) -> Option<Estimate> {
+    let colours = games.palette().len();
+    let handfuls = games.games().iter().flat_map(|game| game.handfuls());
+    let max_total = (0..colours)
+        .map(|colour| u64::from(bounds.max.count(colour)))
+        .sum::<u64>();
+    let ln = LnFactorials::new(max_total);
+    let max_total = max_total as usize;
+
+    // `best[total]` is the largest sum of the colour terms of the colours so
+    // far with `total` cubes of them, and `choices[colour][total]` the number
+    // of cubes of the colour it takes.
+    let mut best = vec![f64::NEG_INFINITY; max_total + 1];
+    best[0] = 0.0;
+    let mut choices = Vec::with_capacity(colours);
+    for colour in 0..colours {
+        let (min, max) = (bounds.min.count(colour), bounds.max.count(colour));
+        let terms = (min..=max)
+            .map(|count| {
+                handfuls
+                    .clone()
+                    .map(|handful| ln.ln_choose(count.into(), handful.count(colour).into()))
+                    .sum::<f64>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut next = vec![f64::NEG_INFINITY; max_total + 1];
+        let mut choice = vec![0; max_total + 1];
+        for (total, &before) in best.iter().enumerate() {
+            if before == f64::NEG_INFINITY {
+                continue;
+            }
+            for (count, term) in (min..=max).zip(&terms) {
+                let total = total + count as usize;
+                if before + term > next[total] {
+                    next[total] = before + term;
+                    choice[total] = count;
+                }
+            }
+        }
+        best = next;
+        choices.push(choice);
+    }
+
+    let (total, ln_likelihood) = best
+        .iter()
+        .enumerate()
+        .map(|(total, &colours)| {
+            let drawn = handfuls
+                .clone()
+                .map(|handful| ln.ln_choose(total as u64, handful.total()))
+                .sum::<f64>();
+            match colours == f64::NEG_INFINITY || drawn == f64::NEG_INFINITY {
+                true => (total, f64::NEG_INFINITY),
+                false => (total, colours - drawn),
+            }
+        })
+        .filter(|&(_, ln_likelihood)| ln_likelihood > f64::NEG_INFINITY)
+        .fold(
+            None,
+            |best: Option<(usize, f64)>, (total, ln_likelihood)| match best {
+                Some((_, best_ln)) if best_ln >= ln_likelihood => best,
+                _ => Some((total, ln_likelihood)),
+            },
+        )?;
+
+    let mut bag = Cubes::default();
+    let mut rest = total;
+    for (colour, choice) in choices.iter().enumerate().rev() {
+        bag.set(colour, choice[rest]);
+        rest -= choice[rest] as usize;
+    }
+    Some(Estimate {
+        games: probabilities(games, &bag),
+        bag,
+        ln_likelihood,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::day02::{parse, tests::EXAMPLE};
+
+    #[test]
+    fn test_handful_probability() {
+        let games = parse(EXAMPLE).unwrap();
+        let bag = Games::puzzle_bag();
+        let handful = &games.games()[0].handfuls()[0];
+        // 3 of the 14 blue and 4 of the 12 red cubes, 7 of all 39.
+        let expected = 364.0 * 495.0 / 15_380_937.0;
+        assert!((handful_probability(&bag, handful) - expected).abs() < 1e-12);
+
+        let probabilities = probabilities(&games, &bag);
+        assert_eq!(probabilities.len(), 5);
+        let possible = probabilities
+            .iter()
+            .filter(|game| game.probability() > 0.0)
+            .map(|game| game.id)
+            .collect::<Vec<_>>();
+        assert_eq!(possible, [1, 2, 5]);
+
+        // More cubes than there are in the bag.
+        let bag = games.bag(b"2 red, 1 blue").unwrap();
+        let handful = games.bag(b"3 red, 1 blue").unwrap();
+        assert_eq!(handful_probability(&bag, &handful), 0.0);
+        let ln = LnFactorials::new(bag.total());
+        assert_eq!(ln.ln_probability(&bag, &handful), f64::NEG_INFINITY);
+        assert!(super::probabilities(&games, &bag)
+            .iter()
+            .all(|game| game.ln_probability == f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn test_likeliest_bag() {
+        let games = parse(b"Game 1: 1 red, 1 blue; 2 red").unwrap();
+        let bounds = Bounds::up_to(games.bag(b"5 red, 5 blue").unwrap());
+        let estimate = likeliest_bag(&games, &bounds).unwrap();
+        // The first handful favours as many blue as red cubes, the second
+        // one a bag of only red ones.
+        assert_eq!(games.palette().format(&estimate.bag), "3 red, 1 blue");
+        assert!((estimate.games[0].probability() - 0.25).abs() < 1e-12);
+        assert_eq!(estimate.ln_likelihood, estimate.games[0].ln_probability);
+
+        let bounds = Bounds::up_to(games.bag(b"1 red, 5 blue").unwrap());
+        assert_eq!(likeliest_bag(&games, &bounds), None);
+    }
+
+    /// Games of up to three handfuls with few cubes of 3 colours.
+    fn games() -> impl Strategy<Value = Vec<u8>> {
+        let handful = proptest::collection::vec(0..4u32, 3).prop_map(|counts| {
+            counts
+                .iter()
+                .zip(["red", "green", "blue"])
+                .map(|(count, colour)| format!("{count} {colour}"))
+                .join(", ")
+        });
+        let game =
+            proptest::collection::vec(handful, 1..=3).prop_map(|handfuls| handfuls.join("; "));
+        proptest::collection::vec(game, 1..=3).prop_map(|games| {
+            games
+                .iter()
+                .enumerate()
+                .map(|(i, game)| format!("Game {}: {game}", i + 1))
+                .join("\n")
+                .into_bytes()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_likeliest_bag_of_all(input in games(), max in [0..6u32, 0..6, 0..6]) {
+            let games = parse(&input).unwrap();
+            let bag = format!("{} red, {} green, {} blue", max[0], max[1], max[2]);
+            let bounds = Bounds::up_to(games.bag(bag.as_bytes()).unwrap());
+            let ln_likelihood = |bag: &Cubes| {
+                probabilities(&games, bag).iter().map(|game| game.ln_probability).sum::<f64>()
+            };
+            let best = (0..=max[0])
+                .cartesian_product(0..=max[1])
+                .cartesian_product(0..=max[2])
+                .map(|((red, green), blue)| {
+                    let mut bag = Cubes::default();
+                    for (colour, count) in [red, green, blue].into_iter().enumerate() {
+                        bag.set(colour, count);
+                    }
+                    ln_likelihood(&bag)
+                })
+                .fold(f64::NEG_INFINITY, f64::max);
+
+            match likeliest_bag(&games, &bounds) {
+                None => prop_assert_eq!(best, f64::NEG_INFINITY),
+                Some(estimate) => {
+                    prop_assert!((estimate.ln_likelihood - best).abs() < 1e-9);
+                    prop_assert!((ln_likelihood(&estimate.bag) - best).abs() < 1e-9);
+                    prop_assert!(bounds.max.contains(&estimate.bag));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! [Day 3: Gear Ratios](https://adventofcode.com/2023/day/3)
+
+use std::collections::HashSet;
+
+use smallvec::smallvec_inline as smallvec;
+
+use crate::{parse::Parser, Grid, ParseError, Solution};
+
+fn is_symbol(c: u8) -> bool {
+    c != b'.' && !c.is_ascii_digit()
+}
+
+fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    Grid::parse_any(&Parser::new(Day03::DAY, input), input)
+}
+
+/// Sum of all part numbers, i.e. numbers adjacent to a symbol, `None` if a
+/// number or the sum doesn't fit in a `u64`.
+pub fn part_1(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|schematic| part_number_sum(&schematic))
+}
+
+/// Sum of the gear ratios of all `*` adjacent to exactly two part numbers,
+/// `None` if a number or the sum doesn't fit in a `u64`.
+pub fn part_2(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|schematic| gear_ratio_sum(&schematic))
+}
+
+/// `number` followed by the digit `c`, `None` if that doesn't fit in a
+/// `u64`.
+fn push_digit(number: u64, c: u8) -> Option<u64> {
+    number.checked_mul(10)?.checked_add(u64::from(c - b'0'))
+}
+
+fn part_number_sum(schematic: &Grid<u8>) -> Option<u64> {
+    let mut grid = Grid::new(schematic.width(), schematic.height(), false);
+    for (x, y) in schematic.positions() {
+        if is_symbol(schematic[(x, y)]) {
+            for n in schematic.neighbors_8(x, y) {
+                grid[n] = true;
+            }
+        }
+    }
+
+    let mut result = 0u64;
+
+    for (y, line) in schematic.rows().enumerate() {
+        let mut temp = 0;
+        let mut encountered_symbol = false;
+        for (x, c) in line.iter().enumerate() {
+            if c.is_ascii_digit() {
+                if encountered_symbol || grid[(x, y)] {
+                    encountered_symbol = true;
+                }
+                temp = push_digit(temp, *c)?;
+                continue;
+            } else if encountered_symbol {
+                result = result.checked_add(temp)?;
+            }
+            temp = 0;
+            encountered_symbol = false;
+        }
+        if encountered_symbol {
+            result = result.checked_add(temp)?;
+        }
+    }
+
+    Some(result)
+}
+
+fn gear_ratio_sum(schematic: &Grid<u8>) -> Option<u64> {
+    let mut grid = Grid::new(schematic.width(), schematic.height(), None);
+    let mut gear_idx = 0;
+    for (x, y) in schematic.positions() {
+        if schematic[(x, y)] == b'*' {
+            for n in schematic.neighbors_8(x, y) {
+                if grid[n].is_none() {
+                    grid[n] = Some(smallvec![gear_idx]);
+                } else {
+                    grid[n].as_mut().unwrap().push(gear_idx);
+                }
+            }
+            gear_idx += 1;
+        }
+    }
+
+    let mut gears = vec![None; gear_idx];
+
+    for (y, line) in schematic.rows().enumerate() {
+        let mut temp = 0;
+        let mut encountered_gears: HashSet<usize> = HashSet::new();
+
+        for (x, &c) in line.iter().enumerate() {
+            if c.is_ascii_digit() {
+                if let Some(gears) = grid[(x, y)].as_ref() {
+                    encountered_gears.extend(gears);
+                }
+                temp = push_digit(temp, c)?;
+                continue;
+            } else {
+                for &gear in &encountered_gears {
+                    if gears[gear].is_none() {
+                        gears[gear] = Some(smallvec![temp]);
+                    } else {
+                        gears[gear].as_mut().unwrap().push(temp);
+                    }
+                }
+                temp = 0;
+                encountered_gears.clear();
+            }
+        }
+        for &gear in &encountered_gears {
+            if gears[gear].is_none() {
+                gears[gear] = Some(smallvec![temp]);
+            } else {
+                gears[gear].as_mut().unwrap().push(temp);
+            }
+        }
+    }
+
+    gears
+        .iter()
+        .flatten()
+        .filter(|x| x.len() == 2)
+        .try_fold(0u64, |sum, x| sum.checked_add(x[0].checked_mul(x[1])?))
+}
+
+/// The [`Solution`] of this day.
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed<'a> = Grid<u8>;
+    type Part1 = Option<u64>;
+    type Part2 = Option<u64>;
+
+    const DAY: u8 = 3;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-03/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(schematic: &Self::Parsed<'_>) -> Self::Part1 {
+        part_number_sum(schematic)
+    }
+
+    fn part_2(schematic: &Self::Parsed<'_>) -> Self::Part2 {
+        gear_ratio_sum(schematic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(Some(4361)));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(Some(467_835)));
+    }
+
+    #[test]
+    fn test_long_numbers() {
+        assert_eq!(part_1(b"12345*.\n......6"), Ok(Some(12_351)));
+        assert_eq!(part_2(b"12345*.\n......6"), Ok(Some(74_070)));
+        assert_eq!(part_1(b"99999999999999999999#"), Ok(None));
+        assert_eq!(part_2(b"4294967296*4294967296"), Ok(None));
+    }
+}
@@ -0,0 +1,168 @@
+//! [Day 4: Scratchcards](https://adventofcode.com/2023/day/4)
+
+use std::{
+    collections::{HashSet, VecDeque},
+    io,
+};
+
+use bstr::ByteSlice;
+
+use crate::{
+    parse::Parser,
+    stream::{self, StreamError},
+    ParseError, Solution,
+};
+
+/// Number of winning numbers on each card.
+fn parse(input: &[u8]) -> Result<Vec<usize>, ParseError> {
+    let parser = Parser::new(Day04::DAY, input);
+    input
+        .lines()
+        .map(|line| parse_card(&parser, line))
+        .collect()
+}
+
+/// Number of winning numbers on the card.
+fn parse_card(parser: &Parser, line: &[u8]) -> Result<usize, ParseError> {
+    let (_, card) = parser.split_once(line, ": ")?;
+    let (winning_numbers, my_numbers) = parser.split_once(card, " | ")?;
+    let winning_numbers = parser
+        .numbers::<u32>(winning_numbers, " ")?
+        .into_iter()
+        .collect::<HashSet<_>>();
+    let my_numbers = parser
+        .numbers::<u32>(my_numbers, " ")?
+        .into_iter()
+        .collect::<HashSet<_>>();
+
+    Ok(winning_numbers.intersection(&my_numbers).count())
+}
+
+/// Total points of all scratchcards, `None` if that doesn't fit in a `u64`.
+pub fn part_1(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|matches| total_points(&matches))
+}
+
+/// Total number of scratchcards once all won copies are processed, `None` if
+/// that doesn't fit in a `u64`.
+pub fn part_2(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|matches| total_cards(&matches))
+}
+
+/// Solves both parts reading one card of `reader` at a time, see
+/// [`stream`](crate::stream). Only the copies won of the next few cards are
+/// kept.
+pub fn solve_stream(reader: impl io::BufRead) -> Result<(Option<u64>, Option<u64>), StreamError> {
+    let mut copies = VecDeque::new();
+    let (mut part_1, mut part_2) = (Some(0u64), Some(0u64));
+    stream::for_each_record(Day04::DAY, reader, b'\n', |parser, line| {
+        let match_count = parse_card(parser, line)?;
+        part_1 = part_1
+            .zip(points(match_count))
+            .and_then(|(sum, p)| sum.checked_add(p));
+        part_2 = part_2
+            .zip(instances(&mut copies, match_count))
+            .and_then(|(sum, n)| sum.checked_add(n));
+        Ok(())
+    })?;
+    Ok((part_1, part_2))
+}
+
+/// Points of a card with `match_count` winning numbers, `None` if they
+/// don't fit in a `u64`.
+fn points(match_count: usize) -> Option<u64> {
+    match match_count.checked_sub(1) {
+        Some(n) => 1u64.checked_shl(u32::try_from(n).ok()?),
+        None => Some(0),
+    }
+}
+
+/// Number of instances of the next card, with the `copies` won of it and
+/// of the cards after it so far. Adds the copies won by these instances.
+fn instances(copies: &mut VecDeque<u64>, match_count: usize) -> Option<u64> {
+    let my_count = copies.pop_front().unwrap_or(1);
+
+    let copies_len = copies.len();
+    for item in copies.iter_mut().take(match_count) {
+        *item = my_count.checked_add(*item)?;
+    }
+    for _ in copies_len..match_count {
+        copies.push_back(my_count.checked_add(1)?);
+    }
+    Some(my_count)
+}
+
+fn total_points(matches: &[usize]) -> Option<u64> {
+    matches.iter().try_fold(0u64, |sum, &match_count| {
+        sum.checked_add(points(match_count)?)
+    })
+}
+
+fn total_cards(matches: &[usize]) -> Option<u64> {
+    let mut copies = VecDeque::new();
+    matches.iter().try_fold(0u64, |total, &match_count| {
+        total.checked_add(instances(&mut copies, match_count)?)
+    })
+}
+
+/// The [`Solution`] of this day.
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed<'a> = Vec<usize>;
+    type Part1 = Option<u64>;
+    type Part2 = Option<u64>;
+
+    const DAY: u8 = 4;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-04/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(matches: &Self::Parsed<'_>) -> Self::Part1 {
+        total_points(matches)
+    }
+
+    fn part_2(matches: &Self::Parsed<'_>) -> Self::Part2 {
+        total_cards(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11    ";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(Some(13)));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(Some(30)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let numbers = (1..=65)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let card = format!("Card 1: {numbers} | {numbers}");
+        assert_eq!(part_1(card.as_bytes()), Ok(None));
+        let cards = (1..=100)
+            .map(|i| format!("Card {i}: 1 2 | 1 2"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(part_2(cards.as_bytes()), Ok(None));
+    }
+}
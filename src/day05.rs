@@ -0,0 +1,346 @@
+//! [Day 5: If You Give A Seed A Fertilizer](https://adventofcode.com/2023/day/5)
+
+use std::ops::Range;
+
+use bstr::ByteSlice;
+
+use crate::{parse::Parser, ParseError, Solution};
+
+/// A line of a map: destination start, source start and length of a range.
+pub type Rule = (u64, u64, u64);
+
+/// The parsed almanac.
+#[derive(Debug, Clone)]
+pub struct Input {
+    seeds: Vec<u64>,
+    seed_to_soil: Vec<Rule>,
+    soil_to_fertilizer: Vec<Rule>,
+    fertilizer_to_water: Vec<Rule>,
+    water_to_light: Vec<Rule>,
+    light_to_temperature: Vec<Rule>,
+    temperature_to_humidity: Vec<Rule>,
+    humidity_to_location: Vec<Rule>,
+}
+
+fn parse_section(parser: &Parser, input: &[u8]) -> Result<Vec<Rule>, ParseError> {
+    let mut lines = input.lines();
+    let header = lines.next().unwrap_or_default();
+    parser.strip_suffix(header, " map:")?;
+    lines
+        .map(|line| {
+            let [dst, src, len]: [u64; 3] = parser.number_array(line, " ")?;
+            if dst.max(src).checked_add(len).is_none() {
+                return Err(parser.error(line, "ranges that end below 2^64"));
+            }
+            Ok((dst, src, len))
+        })
+        .collect()
+}
+
+impl Input {
+    pub fn parse(input: &[u8]) -> Result<Self, ParseError> {
+        let parser = Parser::new(Day05::DAY, input);
+        let mut sections = input.split_str("\n\n").skip(1);
+        let mut next_section = || {
+            let section = sections
+                .next()
+                .ok_or_else(|| parser.error(parser.end(), "another map"))?;
+            parse_section(&parser, section)
+        };
+
+        let seeds_line = input.lines().next().unwrap_or_default();
+        let seeds = parser.numbers(parser.strip_prefix(seeds_line, "seeds: ")?, " ")?;
+        if seeds.is_empty() || seeds.len() % 2 != 0 {
+            return Err(parser.error(seeds_line, "an even, non-zero number of seeds"));
+        }
+        if seeds
+            .chunks_exact(2)
+            .any(|range: &[u64]| range[0].checked_add(range[1]).is_none())
+        {
+            return Err(parser.error(seeds_line, "ranges that end below 2^64"));
+        }
+
+        Ok(Self {
+            seeds,
+            seed_to_soil: next_section()?,
+            soil_to_fertilizer: next_section()?,
+            fertilizer_to_water: next_section()?,
+            water_to_light: next_section()?,
+            light_to_temperature: next_section()?,
+            temperature_to_humidity: next_section()?,
+            humidity_to_location: next_section()?,
+        })
+    }
+
+    /// The sections of the almanac in the order they apply, with their names.
+    fn stages(&self) -> [(&'static str, &[Rule]); 7] {
+        [
+            ("seed-to-soil", &self.seed_to_soil),
+            ("soil-to-fertilizer", &self.soil_to_fertilizer),
+            ("fertilizer-to-water", &self.fertilizer_to_water),
+            ("water-to-light", &self.water_to_light),
+            ("light-to-temperature", &self.light_to_temperature),
+            ("temperature-to-humidity", &self.temperature_to_humidity),
+            ("humidity-to-location", &self.humidity_to_location),
+        ]
+    }
+}
+
+/// Maps `src` through one section of the almanac.
+pub fn map_to_next(map: &[Rule], src: u64) -> u64 {
+    map.iter()
+        .find_map(|&(dst_range, src_range, range_len)| {
+            (src_range..(src_range + range_len))
+                .contains(&src)
+                .then(|| dst_range + (src - src_range))
+        })
+        .unwrap_or(src)
+}
+
+/// Lowest location any of the seeds maps to.
+pub fn lowest_location(parsed: &Input) -> u64 {
+    let stages = parsed.stages();
+    parsed
+        .seeds
+        .iter()
+        .map(|&seed| {
+            stages
+                .iter()
+                .fold(seed, |src, (_, map)| map_to_next(map, src))
+        })
+        .min()
+        .unwrap()
+}
+
+/// Maps a range of sources through one section of the almanac, splitting it
+/// wherever the rules of the section split it.
+pub fn multi_map_to_next(map: &[Rule], src_range: Range<u64>) -> Vec<Range<u64>> {
+    let mut result = Vec::new();
+    let mut unmapped = vec![src_range];
+    while let Some(range) = unmapped.pop() {
+        if range.is_empty() {
+            continue;
+        }
+        let rule = map
+            .iter()
+            .find(|&&(_, src, len)| src < range.end && range.start < src + len);
+        let Some(&(dst, src, len)) = rule else {
+            result.push(range);
+            continue;
+        };
+
+        let (start, end) = (range.start.max(src), range.end.min(src + len));
+        result.push(dst + (start - src)..dst + (end - src));
+        unmapped.push(range.start..start);
+        unmapped.push(end..range.end);
+    }
+    result
+}
+
+/// Sorts `ranges` and merges the ones that overlap or touch.
+pub fn consolidate_ranges(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.retain(|range| !range.is_empty());
+    ranges.sort_unstable_by_key(|range| range.start);
+    let mut result: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match result.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => result.push(range),
+        }
+    }
+    result
+}
+
+/// Lowest location any of the seeds maps to when the seeds line lists
+/// ranges of seeds, `None` if all the ranges are empty.
+pub fn lowest_location_of_ranges(parsed: &Input) -> Option<u64> {
+    let mut ranges = parsed
+        .seeds
+        .chunks_exact(2)
+        .map(|a| a[0]..(a[0] + a[1]))
+        .collect::<Vec<_>>();
+
+    for stage in parsed.stages() {
+        enter_span!("almanac stage", name = %stage.0, ranges = ranges.len());
+        ranges = consolidate_ranges(ranges)
+            .into_iter()
+            .flat_map(|range| multi_map_to_next(stage.1, range))
+            .collect();
+    }
+
+    ranges.into_iter().map(|r| r.start).min()
+}
+
+/// Lowest location any of the seeds maps to.
+pub fn part_1(input: &[u8]) -> Result<u64, ParseError> {
+    Input::parse(input).map(|parsed| lowest_location(&parsed))
+}
+
+/// Lowest location any of the seeds maps to when the seeds line lists ranges
+/// of seeds, `None` if all the ranges are empty.
+pub fn part_2(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    Input::parse(input).map(|parsed| lowest_location_of_ranges(&parsed))
+}
+
+/// The [`Solution`] of this day.
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed<'a> = Input;
+    type Part1 = u64;
+    type Part2 = Option<u64>;
+
+    const DAY: u8 = 5;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-05/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        Input::parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        lowest_location(input)
+    }
+
+    fn part_2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        lowest_location_of_ranges(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(35));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(Some(46)));
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        let input = EXAMPLE.replace(b"seeds: 79 14 55 13", b"seeds: 79 0 55 13");
+        assert_eq!(part_1(&input), Ok(22));
+        assert_eq!(part_2(&input), Ok(Some(56)));
+        let input = EXAMPLE.replace(b"seeds: 79 14 55 13", b"seeds: 79 0 55 0");
+        assert_eq!(part_2(&input), Ok(None));
+    }
+
+    /// A section with rules on disjoint source ranges within `0..200`, like
+    /// the real almanac.
+    fn section() -> impl Strategy<Value = Vec<Rule>> {
+        prop::collection::vec((0..10u64, 1..20u64, 0..200u64), 0..6).prop_map(|rules| {
+            let mut src = 0;
+            rules
+                .into_iter()
+                .map(|(gap, len, dst)| {
+                    src += gap;
+                    let rule = (dst, src, len);
+                    src += len;
+                    rule
+                })
+                .collect()
+        })
+    }
+
+    fn almanac() -> impl Strategy<Value = Input> {
+        (
+            prop::collection::vec((0..200u64, 0..30u64), 1..4),
+            prop::collection::vec(section(), 7),
+        )
+            .prop_map(|(seeds, mut sections)| Input {
+                seeds: seeds
+                    .into_iter()
+                    .flat_map(|(start, len)| [start, len])
+                    .collect(),
+                humidity_to_location: sections.pop().unwrap(),
+                temperature_to_humidity: sections.pop().unwrap(),
+                light_to_temperature: sections.pop().unwrap(),
+                water_to_light: sections.pop().unwrap(),
+                fertilizer_to_water: sections.pop().unwrap(),
+                soil_to_fertilizer: sections.pop().unwrap(),
+                seed_to_soil: sections.pop().unwrap(),
+            })
+    }
+
+    /// Maps every single seed of the ranges.
+    fn lowest_location_of_ranges_naive(almanac: &Input) -> Option<u64> {
+        almanac
+            .seeds
+            .chunks_exact(2)
+            .flat_map(|a| a[0]..a[0] + a[1])
+            .map(|seed| {
+                almanac
+                    .stages()
+                    .iter()
+                    .fold(seed, |src, (_, map)| map_to_next(map, src))
+            })
+            .min()
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2000))]
+
+        #[test]
+        fn test_multi_map_to_next(map in section(), start in 0..250u64, len in 1..40u64) {
+            let mut mapped = multi_map_to_next(&map, start..start + len)
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            let mut expected = (start..start + len)
+                .map(|src| map_to_next(&map, src))
+                .collect::<Vec<_>>();
+            mapped.sort_unstable();
+            expected.sort_unstable();
+            prop_assert_eq!(mapped, expected);
+        }
+
+        #[test]
+        fn test_lowest_location_of_ranges(almanac in almanac()) {
+            prop_assert_eq!(
+                lowest_location_of_ranges(&almanac),
+                lowest_location_of_ranges_naive(&almanac)
+            );
+        }
+    }
+}
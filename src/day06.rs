@@ -0,0 +1,176 @@
+//! [Day 6: Wait For It](https://adventofcode.com/2023/day/6)
+
+use bstr::ByteSlice;
+use itertools::{izip, Itertools};
+
+use crate::{math, parse::Parser, ParseError, Solution};
+
+/// Number of ways to beat the record `distance` in a race lasting `time`.
+pub fn solve(time: i64, distance: i64) -> u64 {
+    // Holding the button for x beats the record if x * (t - x) > d. Neither
+    // 4 * d nor the zeros, between 0 and t, overflow.
+    math::quadratic_below_zero(1, -time, distance).map_or(0, |holds| {
+        (holds.end().min(&time) - holds.start().max(&0) + 1).max(0) as u64
+    })
+}
+
+/// The races of the sheet, read both as separate races and as the single race
+/// hidden by the bad kerning.
+#[derive(Debug, Clone)]
+pub struct Races {
+    races: Vec<(i64, i64)>,
+    kerned: (i64, i64),
+}
+
+/// A time or distance `n` read from `s`, which has to fit in an `i64` for
+/// [`solve`].
+fn to_i64(parser: &Parser, s: &[u8], n: u64) -> Result<i64, ParseError> {
+    i64::try_from(n).map_err(|_| parser.error(s, "a number below 2^63"))
+}
+
+fn parse(input: &[u8]) -> Result<Races, ParseError> {
+    let parser = Parser::new(Day06::DAY, input);
+    let mut lines = input.lines();
+    let mut next_line = |prefix| {
+        let line = lines
+            .next()
+            .ok_or_else(|| parser.error(parser.end(), format!("`{prefix}`")))?;
+        let numbers = parser.strip_prefix(line, prefix)?;
+        let joined = numbers
+            .iter()
+            .filter(|&&c| c != b' ')
+            .copied()
+            .collect::<Vec<u8>>();
+        let separate = numbers
+            .split_str(" ")
+            .filter(|n| !n.is_empty())
+            .map(|n| {
+                parser
+                    .number(n)
+                    .and_then(|number| to_i64(&parser, n, number))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let kerned = std::str::from_utf8(&joined)
+            .ok()
+            .and_then(|n| n.parse::<u64>().ok())
+            .ok_or_else(|| parser.error(numbers.trim(), "a number"))
+            .and_then(|kerned| to_i64(&parser, numbers.trim(), kerned))?;
+
+        Ok((separate, kerned, line))
+    };
+    let (time, kerned_time, _) = next_line("Time:")?;
+    let (distance, kerned_distance, distance_line) = next_line("Distance:")?;
+
+    if time.len() != distance.len() {
+        return Err(parser.error(
+            distance_line,
+            format!("{} distances, one per race", time.len()),
+        ));
+    }
+
+    Ok(Races {
+        races: izip!(time, distance).collect_vec(),
+        kerned: (kerned_time, kerned_distance),
+    })
+}
+
+/// Product of the number of ways to win each race.
+pub fn part_1(input: &[u8]) -> Result<u64, ParseError> {
+    parse(input).map(|races| margin_of_error(&races))
+}
+
+/// Number of ways to win the single race hidden by the bad kerning.
+pub fn part_2(input: &[u8]) -> Result<u64, ParseError> {
+    parse(input).map(|races| solve(races.kerned.0, races.kerned.1))
+}
+
+fn margin_of_error(races: &Races) -> u64 {
+    races
+        .races
+        .iter()
+        .map(|&(time, distance)| solve(time, distance))
+        .product()
+}
+
+/// The [`Solution`] of this day.
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed<'a> = Races;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    const DAY: u8 = 6;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-06/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(races: &Self::Parsed<'_>) -> Self::Part1 {
+        margin_of_error(races)
+    }
+
+    fn part_2(races: &Self::Parsed<'_>) -> Self::Part2 {
+        solve(races.kerned.0, races.kerned.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(288));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(71503));
+    }
+
+    #[test]
+    fn test_too_large() {
+        let err = part_2(b"Time: 92233720 36854775808\nDistance: 1").unwrap_err();
+        assert_eq!(err.expected, "a number below 2^63");
+        let err = part_1(b"Time: 1 9223372036854775808\nDistance: 0 1").unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_ref()),
+            (9, "a number below 2^63")
+        );
+        assert_eq!(
+            part_2(b"Time: 92233720 36854775807\nDistance: 0 0"),
+            Ok(9_223_372_036_854_775_806)
+        );
+    }
+
+    /// Tries every time to hold the button.
+    fn solve_naive(time: i64, distance: i64) -> u64 {
+        (0..=time)
+            .filter(|hold| hold * (time - hold) > distance)
+            .count() as u64
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(5000))]
+
+        #[test]
+        fn test_solve(time in 0..2000i64, distance in 0..1_000_000i64) {
+            prop_assert_eq!(solve(time, distance), solve_naive(time, distance));
+        }
+
+        #[test]
+        fn test_solve_near_record(time in 0..2000i64, offset in 0..3i64) {
+            // Records right at the best distance, where rounding goes wrong.
+            let distance = (time * time / 4).saturating_sub(offset);
+            prop_assert_eq!(solve(time, distance), solve_naive(time, distance));
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! [Day 7: Camel Cards](https://adventofcode.com/2023/day/7)
+
+use std::io;
+
+use bstr::ByteSlice;
+use itertools::Itertools;
+
+use crate::{
+    parse::Parser,
+    stream::{self, StreamError},
+    ParseError, Solution,
+};
+
+#[derive(Debug, PartialOrd, Ord, PartialEq, Eq)]
+enum HandType {
+    HighCard = 0,
+    OnePair = 1,
+    TwoPair = 2,
+    ThreeOfAKind = 3,
+    FullHouse = 4,
+    FourOfAKind = 5,
+    FiveOfAKind = 6,
+}
+
+impl HandType {
+    /// Type of a hand of `cards`, where cards of value `joker` act like
+    /// whichever card makes the strongest type.
+    fn of(cards: [u8; 5], joker: Option<u8>) -> Self {
+        let mut joker_count = 0;
+        let mut frequency = cards
+            .iter()
+            .sorted_unstable()
+            .group_by(|&&c| c)
+            .into_iter()
+            .filter_map(|(c, g)| {
+                if Some(c) == joker {
+                    joker_count = g.count();
+                    None
+                } else {
+                    Some(g.count())
+                }
+            })
+            .sorted_unstable()
+            .collect_vec();
+
+        match frequency.last_mut() {
+            Some(f) => *f += joker_count,
+            None => frequency.push(joker_count),
+        }
+
+        match frequency.as_slice() {
+            [5] => Self::FiveOfAKind,
+            [1, 4] => Self::FourOfAKind,
+            [2, 3] => Self::FullHouse,
+            [1, 1, 3] => Self::ThreeOfAKind,
+            [1, 2, 2] => Self::TwoPair,
+            [1, 1, 1, 2] => Self::OnePair,
+            _ => Self::HighCard,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
+struct Hand {
+    kind: HandType,
+    cards: [u8; 5],
+}
+
+/// Parses the hands with the values of their cards, `2..=14`, and their bids.
+fn parse(input: &[u8]) -> Result<Vec<([u8; 5], u64)>, ParseError> {
+    let parser = Parser::new(Day07::DAY, input);
+    input
+        .lines()
+        .map(|line| parse_hand(&parser, line))
+        .collect()
+}
+
+fn parse_hand(parser: &Parser, line: &[u8]) -> Result<([u8; 5], u64), ParseError> {
+    let (hand_str, bid) = parser.split_once(line, " ")?;
+    let bid = u64::from(parser.number::<u32>(bid)?);
+
+    let cards_in_hand = TryInto::<[u8; 5]>::try_into(hand_str)
+        .map_err(|_| parser.error(hand_str, "a hand of 5 cards"))?;
+    let mut cards = [0; 5];
+    for (i, (card, &c)) in cards.iter_mut().zip(&cards_in_hand).enumerate() {
+        *card = match c {
+            b'2'..=b'9' => c - b'0',
+            b'T' => 10,
+            b'J' => 11,
+            b'Q' => 12,
+            b'K' => 13,
+            b'A' => 14,
+            _ => return Err(parser.error(&hand_str[i..=i], "a card")),
+        };
+    }
+
+    Ok((cards, bid))
+}
+
+/// Total winnings of all hands, `None` if they don't fit in a `u64`.
+pub fn part_1(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|hands| total_winnings(&hands))
+}
+
+/// Total winnings when `J` cards are jokers, `None` if they don't fit in a
+/// `u64`.
+pub fn part_2(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|hands| total_winnings_with_jokers(&hands))
+}
+
+/// Solves both parts reading one hand of `reader` at a time, see
+/// [`stream`](crate::stream). The hands are tallied by their cards, which
+/// takes the same memory however many hands there are.
+pub fn solve_stream(reader: impl io::BufRead) -> Result<(Option<u64>, Option<u64>), StreamError> {
+    let (mut plain, mut jokers) = (Tally::new(false), Tally::new(true));
+    stream::for_each_record(Day07::DAY, reader, b'\n', |parser, line| {
+        let (cards, bid) = parse_hand(parser, line)?;
+        plain.add(cards, bid);
+        jokers.add(cards, bid);
+        Ok(())
+    })?;
+    Ok((plain.winnings(), jokers.winnings()))
+}
+
+/// Number of different hands, 5 cards of 13 values.
+const HAND_COUNT: usize = 13usize.pow(5);
+
+/// Bid sums and counts of hands by their cards, enough to rank them.
+struct Tally {
+    jokers: bool,
+    /// Indexed by the strengths of the cards as base 13 digits, so the
+    /// hands of a type are in the order of their rank. The bids are summed
+    /// in a `u128`, which `u32` bids can't overflow.
+    hands: Vec<(u128, u64)>,
+}
+
+impl Tally {
+    fn new(jokers: bool) -> Self {
+        Self {
+            jokers,
+            hands: vec![(0, 0); HAND_COUNT],
+        }
+    }
+
+    /// Strength of a card of value `2..=14` within its hand, `0..13`.
+    fn strength(&self, card: u8) -> u8 {
+        match card {
+            11 if self.jokers => 0,
+            ..=10 if self.jokers => card - 1,
+            _ => card - 2,
+        }
+    }
+
+    fn add(&mut self, cards: [u8; 5], bid: u64) {
+        let index = cards.iter().fold(0, |index, &card| {
+            index * 13 + usize::from(self.strength(card))
+        });
+        let (bids, count) = &mut self.hands[index];
+        *bids += u128::from(bid);
+        *count += 1;
+    }
+
+    /// Total winnings of the hands, ranked like in [`winnings`].
+    fn winnings(&self) -> Option<u64> {
+        let joker = self.jokers.then_some(0);
+        let kind = |mut index: usize| {
+            let mut strengths = [0; 5];
+            for strength in strengths.iter_mut().rev() {
+                *strength = (index % 13) as u8;
+                index /= 13;
+            }
+            HandType::of(strengths, joker) as usize
+        };
+        let tallied = || {
+            self.hands
+                .iter()
+                .enumerate()
+                .filter(|(_, &(_, count))| count > 0)
+        };
+
+        // Hands weaker than the next one of each type.
+        let mut weaker = [0u64; 7];
+        for (index, &(_, count)) in tallied() {
+            weaker[kind(index)] += count;
+        }
+        let mut total = 0;
+        for hands in &mut weaker {
+            (total, *hands) = (total + *hands, total);
+        }
+
+        tallied().try_fold(0u64, |total, (index, &(bids, count))| {
+            let weaker = &mut weaker[kind(index)];
+            let rank = *weaker + 1;
+            *weaker += count;
+            let bids = u64::try_from(bids).ok()?;
+            rank.checked_mul(bids)?.checked_add(total)
+        })
+    }
+}
+
+fn total_winnings(hands: &[([u8; 5], u64)]) -> Option<u64> {
+    winnings(hands.iter().map(|&(cards, bid)| {
+        let kind = HandType::of(cards, None);
+        (Hand { kind, cards }, bid)
+    }))
+}
+
+fn total_winnings_with_jokers(hands: &[([u8; 5], u64)]) -> Option<u64> {
+    winnings(hands.iter().map(|&(cards, bid)| {
+        let cards = cards.map(|c| if c == 11 { 0 } else { c });
+        let kind = HandType::of(cards, Some(0));
+        (Hand { kind, cards }, bid)
+    }))
+}
+
+/// Total winnings of `hands` with their bids, `None` if they don't fit in a
+/// `u64`. Equal hands tie for the lowest of their ranks.
+fn winnings(hands: impl Iterator<Item = (Hand, u64)>) -> Option<u64> {
+    let hands = hands
+        .sorted_unstable_by(|(a, _), (b, _)| a.cmp(b))
+        .collect_vec();
+    let mut rank = 0;
+    hands
+        .iter()
+        .enumerate()
+        .try_fold(0u64, |total, (i, (hand, bid))| {
+            if i == 0 || hands[i - 1].0 != *hand {
+                rank = i as u64 + 1;
+            }
+            rank.checked_mul(*bid)?.checked_add(total)
+        })
+}
+
+/// The [`Solution`] of this day.
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed<'a> = Vec<([u8; 5], u64)>;
+    type Part1 = Option<u64>;
+    type Part2 = Option<u64>;
+
+    const DAY: u8 = 7;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-07/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(hands: &Self::Parsed<'_>) -> Self::Part1 {
+        total_winnings(hands)
+    }
+
+    fn part_2(hands: &Self::Parsed<'_>) -> Self::Part2 {
+        total_winnings_with_jokers(hands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(Some(6440)));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(Some(5905)));
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        let generator = crate::generate::generator(Day07::DAY).unwrap();
+        let many = generator.generate(20_000, 7).unwrap();
+        let inputs = [
+            EXAMPLE,
+            many.as_bytes(),
+            b"AAAAA 5\n23456 1\nAAAAA 3\nJJJJJ 7",
+        ];
+        for input in inputs {
+            assert_eq!(
+                solve_stream(input).unwrap(),
+                (part_1(input).unwrap(), part_2(input).unwrap())
+            );
+        }
+        assert_eq!(part_1(inputs[2]), Ok(Some(1 + 2 * 7 + 3 * 8)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Distinct hands, so their ranks add up to more than `u64::MAX / bid`.
+        let hands = (0..100_000u32)
+            .map(|i| {
+                let cards = (0..5)
+                    .map(|digit| char::from(b"23456789TJQKA"[(i / 13u32.pow(digit) % 13) as usize]))
+                    .collect::<String>();
+                format!("{cards} {}", u32::MAX)
+            })
+            .join("\n");
+        assert_eq!(part_1(hands.as_bytes()), Ok(None));
+        assert_eq!(solve_stream(hands.as_bytes()).unwrap(), (None, None));
+    }
+}
@@ -0,0 +1,193 @@
+//! [Day 8: Haunted Wasteland](https://adventofcode.com/2023/day/8)
+
+use bstr::ByteSlice;
+use fnv::FnvHashMap;
+
+use crate::{math, parse::Parser, ParseError, Solution};
+
+/// The instructions, as indices into the `[left, right]` pairs of the nodes,
+/// and the nodes.
+#[derive(Debug, Clone)]
+pub struct Network {
+    instructions: Vec<usize>,
+    nodes: FnvHashMap<[u8; 3], [[u8; 3]; 2]>,
+}
+
+fn parse(input: &[u8]) -> Result<Network, ParseError> {
+    let parser = Parser::new(Day08::DAY, input);
+    let (instructions, map) = parser.split_once(input, "\n\n")?;
+    let instructions = instructions
+        .iter()
+        .enumerate()
+        .map(|(i, c)| match c {
+            b'L' => Ok(0),
+            b'R' => Ok(1),
+            _ => Err(parser.error(&instructions[i..=i], "`L` or `R`")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if instructions.is_empty() {
+        return Err(parser.error(&input[..0], "`L` or `R`"));
+    }
+
+    let node = |name: &[u8]| {
+        TryInto::<[u8; 3]>::try_into(name)
+            .map_err(|_| parser.error(name, "a node name of 3 characters"))
+    };
+    let mut references = Vec::new();
+    let nodes = map
+        .lines()
+        .map(|line| {
+            let (key, targets) = parser.split_once(line, " = ")?;
+            let targets = parser.strip_suffix(parser.strip_prefix(targets, "(")?, ")")?;
+            let (left, right) = parser.split_once(targets, ", ")?;
+            references.extend([left, right]);
+            Ok((node(key)?, [node(left)?, node(right)?]))
+        })
+        .collect::<Result<FnvHashMap<_, _>, _>>()?;
+
+    if let Some(missing) = references.into_iter().find(|&name| {
+        TryInto::<[u8; 3]>::try_into(name).is_ok_and(|name| !nodes.contains_key(&name))
+    }) {
+        return Err(parser.error(missing, "the name of a node"));
+    }
+
+    Ok(Network {
+        instructions,
+        nodes,
+    })
+}
+
+/// Steps from `AAA` to `ZZZ`, `None` if there is no way there.
+pub fn part_1(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|network| steps_to_zzz(&network))
+}
+
+/// Steps until every path starting at a `..A` node is on a `..Z` node at
+/// the same time, `None` if some path never gets to one or the steps don't
+/// fit in a `u64`.
+pub fn part_2(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|network| ghost_steps(&network))
+}
+
+/// Steps from `start` to the first node matching `is_end`, if any. There
+/// are only so many pairs of node and instruction, after visiting all of
+/// them the path loops.
+fn steps_until(
+    network: &Network,
+    start: [u8; 3],
+    is_end: impl Fn(&[u8; 3]) -> bool,
+) -> Option<u64> {
+    let states = network.nodes.len() * network.instructions.len();
+    let mut cursor = start;
+    for (i, &instruction) in network.instructions.iter().cycle().take(states).enumerate() {
+        cursor = network.nodes[&cursor][instruction];
+        if is_end(&cursor) {
+            return Some(1 + i as u64);
+        }
+    }
+    None
+}
+
+fn steps_to_zzz(network: &Network) -> Option<u64> {
+    const AAA: [u8; 3] = [b'A'; 3];
+    if !network.nodes.contains_key(&AAA) {
+        return None;
+    }
+    steps_until(network, AAA, |node| *node == [b'Z'; 3])
+}
+
+fn ghost_steps(network: &Network) -> Option<u64> {
+    let cursors = network
+        .nodes
+        .keys()
+        .filter(|key| matches!(key, [_, _, b'A']))
+        .copied();
+    cursors
+        .map(|start| steps_until(network, start, |node| matches!(node, [_, _, b'Z'])))
+        .try_fold(1, |steps, cycle| math::lcm(steps, cycle?))
+}
+
+/// The [`Solution`] of this day.
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed<'a> = Network;
+    type Part1 = Option<u64>;
+    type Part2 = Option<u64>;
+
+    const DAY: u8 = 8;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-08/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(network: &Self::Parsed<'_>) -> Self::Part1 {
+        steps_to_zzz(network)
+    }
+
+    fn part_2(network: &Self::Parsed<'_>) -> Self::Part2 {
+        ghost_steps(network)
+    }
+
+    fn warnings(network: &Self::Parsed<'_>) -> Vec<String> {
+        if network.nodes.contains_key(b"AAA") {
+            return Vec::new();
+        }
+        vec!["part 1 starts at node `AAA`, which is missing".to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_01A: &[u8] = b"RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE_01B: &[u8] = b"LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE_02: &[u8] = b"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE_01A), Ok(Some(2)));
+        assert_eq!(part_1(EXAMPLE_01B), Ok(Some(6)));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE_02), Ok(Some(6)));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        assert_eq!(part_1(EXAMPLE_02), Ok(None));
+        assert_eq!(part_1(b"L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)"), Ok(None));
+        assert_eq!(
+            part_2(b"R\n\n11A = (11Z, 11B)\n11B = (11B, 11B)\n11Z = (11Z, 11Z)"),
+            Ok(None)
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! [Day 9: Mirage Maintenance](https://adventofcode.com/2023/day/9)
+
+use std::io;
+
+use bstr::ByteSlice;
+
+use crate::{
+    math,
+    parse::Parser,
+    stream::{self, StreamError},
+    ParseError, Solution,
+};
+
+fn parse(input: &[u8]) -> Result<Vec<Vec<i64>>, ParseError> {
+    let parser = Parser::new(Day09::DAY, input);
+    input
+        .lines()
+        .map(|line| parse_history(&parser, line))
+        .collect()
+}
+
+fn parse_history(parser: &Parser, line: &[u8]) -> Result<Vec<i64>, ParseError> {
+    let history = parser.numbers(line, " ")?;
+    // Keeps the sums of the extrapolated values in an `i64`.
+    let fits = |x| math::checked_extrapolate(&history, x).is_some_and(|v| i32::try_from(v).is_ok());
+    if !fits(history.len() as i64) || !fits(-1) {
+        return Err(parser.error(line, "a history extrapolating to 32-bit values"));
+    }
+    Ok(history)
+}
+
+/// Sum of the extrapolated next values of all histories.
+pub fn part_1(input: &[u8]) -> Result<i64, ParseError> {
+    parse(input).map(|histories| extrapolate_next(&histories))
+}
+
+/// Sum of the extrapolated previous values of all histories.
+pub fn part_2(input: &[u8]) -> Result<i64, ParseError> {
+    parse(input).map(|histories| extrapolate_previous(&histories))
+}
+
+/// Solves both parts reading one history of `reader` at a time, see
+/// [`stream`](crate::stream).
+pub fn solve_stream(reader: impl io::BufRead) -> Result<(i64, i64), StreamError> {
+    let (mut part_1, mut part_2) = (0, 0);
+    stream::for_each_record(Day09::DAY, reader, b'\n', |parser, line| {
+        let history = parse_history(parser, line)?;
+        part_1 += math::extrapolate(&history, history.len() as i64);
+        part_2 += math::extrapolate(&history, -1);
+        Ok(())
+    })?;
+    Ok((part_1, part_2))
+}
+
+fn extrapolate_next(histories: &[Vec<i64>]) -> i64 {
+    histories
+        .iter()
+        .map(|history| math::extrapolate(history, history.len() as i64))
+        .sum()
+}
+
+fn extrapolate_previous(histories: &[Vec<i64>]) -> i64 {
+    histories
+        .iter()
+        .map(|history| math::extrapolate(history, -1))
+        .sum()
+}
+
+/// The [`Solution`] of this day.
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed<'a> = Vec<Vec<i64>>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    const DAY: u8 = 9;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-09/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(histories: &Self::Parsed<'_>) -> Self::Part1 {
+        extrapolate_next(histories)
+    }
+
+    fn part_2(histories: &Self::Parsed<'_>) -> Self::Part2 {
+        extrapolate_previous(histories)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(114));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(2));
+    }
+}
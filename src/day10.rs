@@ -0,0 +1,285 @@
+//! [Day 10: Pipe Maze](https://adventofcode.com/2023/day/10)
+
+use std::iter;
+
+use bstr::ByteSlice;
+use itertools::{izip, Itertools};
+
+use crate::{
+    parse::Parser,
+    viz::{self, Frame, Sink, VizError},
+    Grid, ParseError, Solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Facing {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Facing {
+    const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Self::North => (0, -1),
+            Self::East => (1, 0),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+        }
+    }
+
+    /// Facing after following the pipe `tile` when entering it facing
+    /// `self`, `None` if the pipe doesn't connect back.
+    fn turn(self, tile: u8) -> Option<Self> {
+        Some(match (self, tile) {
+            (Self::North, b'|') | (Self::East, b'J') | (Self::West, b'L') => Self::North,
+            (Self::East, b'-') | (Self::North, b'F') | (Self::South, b'L') => Self::East,
+            (Self::South, b'|') | (Self::East, b'7') | (Self::West, b'F') => Self::South,
+            (Self::West, b'-') | (Self::North, b'7') | (Self::South, b'J') => Self::West,
+            _ => return None,
+        })
+    }
+}
+
+/// Directions in which the pipes next to `start` connect to it.
+fn start_facings(grid: &Grid<u8>, start: (usize, usize)) -> impl Iterator<Item = Facing> + '_ {
+    Facing::ALL.into_iter().filter(move |facing| {
+        grid.step(start, facing.offset())
+            .is_some_and(|next| facing.turn(grid[next]).is_some())
+    })
+}
+
+fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    let parser = Parser::new(Day10::DAY, input);
+    let grid = Grid::parse(&parser, input, b"|-LJ7F.S")?;
+    let mut starts = input.find_iter(b"S");
+    match (starts.next(), starts.next()) {
+        (Some(_), None) => {}
+        (None, _) => return Err(parser.error(parser.end(), "a starting tile `S`")),
+        (Some(_), Some(i)) => return Err(parser.error(&input[i..=i], "a single starting tile")),
+    }
+
+    // Both parts follow the loop from the start, so it has to be closed.
+    let tile_at = |(x, y): (usize, usize)| {
+        let line = input.lines().nth(y).unwrap_or_default();
+        &line[x..=x]
+    };
+    let start = grid.position(|&c| c == b'S').unwrap();
+    let Some((mut facing, _)) = start_facings(&grid, start).collect_tuple() else {
+        return Err(parser.error(tile_at(start), "a start connected to exactly two pipes"));
+    };
+    let mut cursor = grid.step(start, facing.offset()).unwrap();
+    while cursor != start {
+        facing = facing.turn(grid[cursor]).unwrap();
+        let next = grid.step(cursor, facing.offset());
+        match next.filter(|&next| next == start || facing.turn(grid[next]).is_some()) {
+            Some(next) => cursor = next,
+            None => return Err(parser.error(tile_at(cursor), "a pipe continuing the loop")),
+        }
+    }
+    Ok(grid)
+}
+
+/// Steps along the loop to the point farthest from the start.
+pub fn part_1(input: &[u8]) -> Result<usize, ParseError> {
+    parse(input).map(|grid| farthest_steps(&grid))
+}
+
+/// Number of tiles enclosed by the loop.
+pub fn part_2(input: &[u8]) -> Result<i64, ParseError> {
+    parse(input).map(|grid| enclosed_tiles(&grid))
+}
+
+/// The tiles of the loop in order, from the start around back to it.
+fn loop_tiles(grid: &Grid<u8>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let start = grid.position(|&c| c == b'S').unwrap();
+    let mut facing = start_facings(grid, start).next().unwrap();
+    let mut cursor = Some(start);
+    iter::from_fn(move || {
+        let current = cursor?;
+        let next = grid.step(current, facing.offset()).unwrap();
+        cursor = (next != start).then(|| {
+            facing = facing.turn(grid[next]).unwrap();
+            next
+        });
+        Some(current)
+    })
+}
+
+fn farthest_steps(grid: &Grid<u8>) -> usize {
+    loop_tiles(grid).count() / 2
+}
+
+fn enclosed_tiles(grid: &Grid<u8>) -> i64 {
+    enclosed(grid)
+        .cells()
+        .iter()
+        .filter(|&&inside| inside)
+        .count() as i64
+}
+
+/// Which tiles the loop encloses.
+fn enclosed(grid: &Grid<u8>) -> Grid<bool> {
+    let mut is_loop = Grid::new(grid.width(), grid.height(), false);
+    for position in loop_tiles(grid) {
+        is_loop[position] = true;
+    }
+
+    let start = grid.position(|&c| c == b'S').unwrap();
+    let start_replacement_tile = match start_facings(grid, start).collect_tuple().unwrap() {
+        (Facing::North, Facing::East) => b'L',
+        (Facing::North, Facing::South) => b'|',
+        (Facing::North, Facing::West) => b'J',
+        (Facing::East, Facing::South) => b'F',
+        (Facing::East, Facing::West) => b'-',
+        (Facing::South, Facing::West) => b'7',
+        _ => unreachable!(),
+    };
+    let mut grid = grid.clone();
+    grid[start] = start_replacement_tile;
+
+    let mut result = Grid::new(grid.width(), grid.height(), false);
+    for (y, (is_loop, row)) in izip!(is_loop.rows(), grid.rows()).enumerate() {
+        let mut inside = false;
+        let mut last_tile = None;
+        for (x, (&is_loop, &tile)) in izip!(is_loop.iter(), row.iter()).enumerate() {
+            if is_loop {
+                if matches!(tile, b'|' | b'L' | b'F') {
+                    inside = !inside;
+                    last_tile = Some(tile);
+                } else if matches!((last_tile, tile), (Some(b'L'), b'J') | (Some(b'F'), b'7')) {
+                    inside = !inside;
+                    last_tile = None;
+                }
+                continue;
+            }
+
+            last_tile = None;
+            result[(x, y)] = inside;
+        }
+    }
+
+    result
+}
+
+/// Draws the loop growing from the start, then the tiles it encloses, see
+/// [`viz`].
+pub fn visualize(input: &[u8], sink: &mut dyn Sink) -> Result<(), VizError> {
+    let grid = parse(input)?;
+    let mut frame = Frame::new(&grid, |&tile| {
+        if tile == b'.' {
+            viz::BACKGROUND
+        } else {
+            viz::FEATURE
+        }
+    });
+    let tiles = loop_tiles(&grid).collect_vec();
+    // About 100 frames however long the loop is.
+    for chunk in tiles.chunks(tiles.len().div_ceil(100)) {
+        sink.frame(frame.paint(chunk.iter().copied(), viz::PATH))?;
+    }
+
+    let inside = enclosed(&grid);
+    frame
+        .paint(
+            grid.positions().filter(|&position| inside[position]),
+            viz::REACHED,
+        )
+        .paint([tiles[0]], viz::MARK);
+    sink.frame(&frame)?;
+    Ok(())
+}
+
+/// The [`Solution`] of this day.
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed<'a> = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = i64;
+
+    const DAY: u8 = 10;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-10/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(grid: &Self::Parsed<'_>) -> Self::Part1 {
+        farthest_steps(grid)
+    }
+
+    fn part_2(grid: &Self::Parsed<'_>) -> Self::Part2 {
+        enclosed_tiles(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_01_A: &[u8] = b"-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    const EXAMPLE_01_B: &[u8] = b"7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ";
+
+    const EXAMPLE_02_A: &[u8] = b"...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const EXAMPLE_02_B: &[u8] = b".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE_01_A), Ok(4));
+        assert_eq!(part_1(EXAMPLE_01_B), Ok(8));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE_02_A), Ok(4));
+        assert_eq!(part_2(EXAMPLE_02_B), Ok(8));
+    }
+
+    #[test]
+    fn test_visualize() {
+        let mut frames = Vec::new();
+        visualize(EXAMPLE_02_A, &mut frames).unwrap();
+        let last = frames.last().unwrap().pixels.cells();
+        assert_eq!(last.iter().filter(|&&c| c == viz::REACHED).count(), 4);
+        assert_eq!(last.iter().filter(|&&c| c == viz::PATH).count(), 45);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = part_1(b"S-7\n|.|\nL-|").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.expected, "a pipe continuing the loop");
+    }
+}
@@ -0,0 +1,178 @@
+//! [Day 11: Cosmic Expansion](https://adventofcode.com/2023/day/11)
+
+use bstr::ByteSlice;
+use itertools::{izip, Itertools};
+
+use crate::{parse::Parser, ParseError, Solution};
+
+/// Positions of the galaxies.
+fn parse(input: &[u8]) -> Result<Vec<(usize, usize)>, ParseError> {
+    Parser::new(Day11::DAY, input).grid(input, b".#")?;
+    Ok(input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| memchr::memchr_iter(b'#', line).map(move |x| (x, y)))
+        .collect_vec())
+}
+
+/// [`solve`] with every empty row and column doubled.
+pub fn part_1(input: &[u8]) -> Result<Option<usize>, ParseError> {
+    solve(input, 2)
+}
+
+/// [`solve`] with every empty row and column replaced by a million.
+pub fn part_2(input: &[u8]) -> Result<Option<usize>, ParseError> {
+    solve(input, 1_000_000)
+}
+
+fn solve_dimension(
+    it: impl Iterator<Item = usize>,
+    expansion_factor: usize,
+    stars_len: usize,
+) -> Option<usize> {
+    let mut state = 0;
+    it.sorted_unstable()
+        .tuple_windows()
+        .zip(izip!((0..stars_len).rev(), 0..))
+        .try_fold(0, |sum, ((curr, next), (to_add, to_remove))| {
+            state = state + to_add - to_remove;
+            let gap = match next - curr {
+                0 => 0,
+                n => expansion_factor.checked_mul(n - 1)?.checked_add(1)?,
+            };
+            state.checked_mul(gap)?.checked_add(sum)
+        })
+}
+
+/// Sum of the shortest paths between all pairs of galaxies, after each empty
+/// row and column is replaced by `expansion_factor` empty ones, `None` if that
+/// doesn't fit in a `usize`.
+pub fn solve(input: &[u8], expansion_factor: usize) -> Result<Option<usize>, ParseError> {
+    parse(input).map(|stars| distance_sum(&stars, expansion_factor))
+}
+
+fn distance_sum(stars: &[(usize, usize)], expansion_factor: usize) -> Option<usize> {
+    let stars_len = stars.len();
+
+    let xs = solve_dimension(stars.iter().map(|(x, _)| *x), expansion_factor, stars_len)?;
+    let ys = solve_dimension(stars.iter().map(|(_, y)| *y), expansion_factor, stars_len)?;
+    xs.checked_add(ys)
+}
+
+/// The [`Solution`] of this day.
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed<'a> = Vec<(usize, usize)>;
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
+
+    const DAY: u8 = 11;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-11/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(stars: &Self::Parsed<'_>) -> Self::Part1 {
+        distance_sum(stars, 2)
+    }
+
+    fn part_2(stars: &Self::Parsed<'_>) -> Self::Part2 {
+        distance_sum(stars, 1_000_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(Some(374)));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(solve(EXAMPLE, 10), Ok(Some(1030)));
+        assert_eq!(solve(EXAMPLE, 100), Ok(Some(8410)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let image = "#.".repeat(100_000);
+        assert!(part_1(image.as_bytes()).unwrap().is_some());
+        assert_eq!(part_2(image.as_bytes()), Ok(None));
+    }
+
+    /// Expands the image cell by cell and sums the distances of all pairs.
+    fn solve_naive(image: &[Vec<bool>], expansion_factor: usize) -> usize {
+        let expanded_offsets = |empty: Vec<bool>| {
+            empty
+                .iter()
+                .scan(0, |offset, &empty| {
+                    let position = *offset;
+                    *offset += if empty { expansion_factor } else { 1 };
+                    Some(position)
+                })
+                .collect::<Vec<_>>()
+        };
+        let width = image[0].len();
+        let xs = expanded_offsets(
+            (0..width)
+                .map(|x| image.iter().all(|row| !row[x]))
+                .collect(),
+        );
+        let ys = expanded_offsets(image.iter().map(|row| row.iter().all(|&c| !c)).collect());
+
+        let galaxies = (0..image.len())
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| image[y][x])
+            .map(|(x, y)| (xs[x], ys[y]))
+            .collect::<Vec<_>>();
+        galaxies
+            .iter()
+            .tuple_combinations()
+            .map(|(a, b)| a.0.abs_diff(b.0) + a.1.abs_diff(b.1))
+            .sum()
+    }
+
+    fn image() -> impl Strategy<Value = Vec<Vec<bool>>> {
+        (1..12usize, 1..12usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(
+                prop::collection::vec(prop::bool::weighted(0.2), width),
+                height,
+            )
+        })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2000))]
+
+        #[test]
+        fn test_solve(image in image(), expansion_factor in 1..1000usize) {
+            let input = image
+                .iter()
+                .map(|row| row.iter().map(|&c| if c { '#' } else { '.' }).collect::<String>())
+                .join("\n");
+            prop_assert_eq!(
+                solve(input.as_bytes(), expansion_factor),
+                Ok(Some(solve_naive(&image, expansion_factor)))
+            );
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! [Day 12: Hot Springs](https://adventofcode.com/2023/day/12)
+
+use std::{collections::BTreeMap, io};
+
+use bstr::ByteSlice;
+use itertools::Itertools;
+use rayon::prelude::*;
+
+use crate::{
+    parse::Parser,
+    stream::{self, StreamError},
+    ParseError, Solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Damaged,
+    Operational,
+}
+
+/// A row of springs, `None` where the condition is unknown, and the sizes of
+/// its groups of damaged springs.
+#[derive(Debug, Clone)]
+pub struct Row {
+    states: Vec<Option<State>>,
+    consecutive_damaged: Vec<usize>,
+}
+
+/// Longest row, which keeps the sum of the arrangements of the unfolded rows
+/// in a `u128`.
+const MAX_SPRINGS: usize = 20;
+
+fn parse(input: &[u8]) -> Result<Vec<Row>, ParseError> {
+    let parser = Parser::new(Day12::DAY, input);
+    input.lines().map(|line| parse_row(&parser, line)).collect()
+}
+
+fn parse_row(parser: &Parser, line: &[u8]) -> Result<Row, ParseError> {
+    let (states, consecutive_damaged) = parser.split_once(line, " ")?;
+    if states.len() > MAX_SPRINGS {
+        return Err(parser.error(states, format!("at most {MAX_SPRINGS} springs")));
+    }
+    let states = states
+        .iter()
+        .enumerate()
+        .map(|(i, c)| match c {
+            b'.' => Ok(Some(State::Operational)),
+            b'#' => Ok(Some(State::Damaged)),
+            b'?' => Ok(None),
+            _ => Err(parser.error(&states[i..=i], "`.`, `#` or `?`")),
+        })
+        .collect::<Result<_, _>>()?;
+    let consecutive_damaged = consecutive_damaged
+        .split_str(",")
+        .map(|n| parser.number(n))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Row {
+        states,
+        consecutive_damaged,
+    })
+}
+
+/// Sum of the possible arrangements of the damaged springs of all rows.
+pub fn part_1(input: &[u8]) -> Result<u128, ParseError> {
+    solve(input, false)
+}
+
+/// Like [`part_1`], but with every row unfolded five times.
+pub fn part_2(input: &[u8]) -> Result<u128, ParseError> {
+    solve(input, true)
+}
+
+fn collect_map_sum(
+    map: impl Iterator<Item = ((usize, usize), u128)>,
+) -> BTreeMap<(usize, usize), u128> {
+    map.fold(BTreeMap::default(), |mut acc, ((damaged, stage), count)| {
+        acc.entry((damaged, stage))
+            .and_modify(|c| *c += count)
+            .or_insert(count);
+        acc
+    })
+}
+
+/// Sum of the possible arrangements of all rows, unfolding each row five
+/// times first if `unfold` is set.
+pub fn solve(input: &[u8], unfold: bool) -> Result<u128, ParseError> {
+    parse(input).map(|rows| arrangements(&rows, unfold))
+}
+
+/// Rows solved in parallel at a time by [`solve_stream`].
+const STREAM_BATCH: usize = 1 << 12;
+
+/// Solves both parts reading a batch of rows of `reader` at a time, see
+/// [`stream`](crate::stream).
+pub fn solve_stream(reader: impl io::BufRead) -> Result<(u128, u128), StreamError> {
+    let (mut part_1, mut part_2) = (0, 0);
+    let mut batch = Vec::with_capacity(STREAM_BATCH);
+    let mut solve_batch = |batch: &mut Vec<Row>| {
+        part_1 += arrangements(batch, false);
+        part_2 += arrangements(batch, true);
+        batch.clear();
+    };
+    stream::for_each_record(Day12::DAY, reader, b'\n', |parser, line| {
+        batch.push(parse_row(parser, line)?);
+        if batch.len() == STREAM_BATCH {
+            solve_batch(&mut batch);
+        }
+        Ok(())
+    })?;
+    solve_batch(&mut batch);
+    Ok((part_1, part_2))
+}
+
+fn arrangements(rows: &[Row], unfold: bool) -> u128 {
+    rows.par_iter()
+        .map(|row| row_arrangements(row, unfold))
+        .sum()
+}
+
+/// Possible arrangements of the damaged springs of a single row.
+fn row_arrangements(row: &Row, unfold: bool) -> u128 {
+    let mut states = row.states.clone();
+    let mut consecutive_damaged = row.consecutive_damaged.clone();
+
+    if unfold {
+        let bak = states.clone();
+        for _ in 0..4 {
+            states.push(None);
+            states.extend_from_slice(&bak);
+        }
+
+        consecutive_damaged = (0..5)
+            .flat_map(|_| consecutive_damaged.clone())
+            .collect_vec();
+    }
+
+    let mut posssible_choices = BTreeMap::from_iter([((0, 0), 1)]);
+    for state in states {
+        posssible_choices = match state {
+            Some(State::Damaged) => posssible_choices
+                .into_iter()
+                .filter_map(|choice| {
+                    let ((mut damaged, stage), count) = choice;
+                    damaged += 1;
+                    (damaged <= consecutive_damaged[stage]).then_some(((damaged, stage), count))
+                })
+                .collect(),
+            Some(State::Operational) => {
+                collect_map_sum(posssible_choices.into_iter().filter_map(|choice| {
+                    let ((damaged, stage), count) = choice;
+                    match damaged {
+                        0 => Some(choice),
+                        n if n == consecutive_damaged[stage] => {
+                            if stage == consecutive_damaged.len() - 1 {
+                                Some(choice)
+                            } else {
+                                Some(((0, stage + 1), count))
+                            }
+                        }
+                        _ => None,
+                    }
+                }))
+            }
+            None => {
+                collect_map_sum(posssible_choices.into_iter().flat_map(|choice| {
+                    let ((damaged, stage), count) = choice;
+
+                    // A) is Operational a valid choice?
+                    let choice_operational =
+                        (damaged == 0 || damaged == consecutive_damaged[stage]).then(|| {
+                            if damaged != 0 && stage != consecutive_damaged.len() - 1 {
+                                ((0, stage + 1), count)
+                            } else {
+                                choice
+                            }
+                        });
+
+                    // B) is Damaged a valid choice?
+                    let choice_damaged = (damaged < consecutive_damaged[stage])
+                        .then(|| ((damaged + 1, stage), count));
+
+                    choice_operational.into_iter().chain(choice_damaged)
+                }))
+            }
+        }
+    }
+    posssible_choices
+        .into_iter()
+        .filter_map(|((damaged, stage), count)| {
+            (stage == consecutive_damaged.len() - 1 && damaged == consecutive_damaged[stage])
+                .then_some(count)
+        })
+        .sum()
+}
+
+/// The [`Solution`] of this day.
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed<'a> = Vec<Row>;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    const DAY: u8 = 12;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-12/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(rows: &Self::Parsed<'_>) -> Self::Part1 {
+        arrangements(rows, false)
+    }
+
+    fn part_2(rows: &Self::Parsed<'_>) -> Self::Part2 {
+        arrangements(rows, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(21));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(525_152));
+    }
+}
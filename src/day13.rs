@@ -0,0 +1,142 @@
+//! [Day 13: Point of Incidence](https://adventofcode.com/2023/day/13)
+
+use bstr::ByteSlice;
+use itertools::{izip, Itertools};
+
+use crate::{parse::Parser, Grid, ParseError, Solution};
+
+fn find_palindrome(input: &[u32], should_smudge: bool) -> usize {
+    (1..input.len())
+        .find(|&center| {
+            let (l, r) = input.split_at(center);
+            let mut smudged = false;
+            izip!(l.iter().rev(), r.iter()).all(|(l, r)| match l == r {
+                true => true,
+                false if should_smudge && !smudged => {
+                    let can_smudge = (l ^ r).count_ones() == 1;
+                    if can_smudge {
+                        smudged = true;
+                        true
+                    } else {
+                        false
+                    }
+                }
+                _ => false,
+            }) && (!should_smudge || smudged)
+        })
+        .unwrap_or(0)
+}
+
+/// A pattern with its rows and columns as bitmasks of the rocks.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    cols: Vec<u32>,
+    rows: Vec<u32>,
+}
+
+/// Bitmask of the rocks in a row or column.
+fn rocks<'a>(line: impl Iterator<Item = &'a u8>) -> u32 {
+    line.enumerate()
+        .fold(0, |acc, (i, c)| acc | (u32::from(*c == b'#') << i))
+}
+
+fn parse(input: &[u8]) -> Result<Vec<Pattern>, ParseError> {
+    let parser = Parser::new(Day13::DAY, input);
+    input
+        .split_str("\n\n")
+        .map(|map| {
+            let grid = Grid::parse(&parser, map, b".#")?;
+            if grid.width() > 32 || grid.height() > 32 {
+                return Err(parser.error(map, "a pattern of at most 32 by 32"));
+            }
+
+            let cols = grid.columns().map(rocks).collect_vec();
+            let rows = grid.rows().map(|row| rocks(row.iter())).collect_vec();
+
+            Ok(Pattern { cols, rows })
+        })
+        .collect()
+}
+
+/// Summary of the reflection lines of all patterns, with `should_smudge`
+/// each reflection has to be off by exactly one smudge.
+pub fn solve(input: &[u8], should_smudge: bool) -> Result<usize, ParseError> {
+    parse(input).map(|patterns| summarize(&patterns, should_smudge))
+}
+
+#[inline]
+fn summarize(patterns: &[Pattern], should_smudge: bool) -> usize {
+    patterns
+        .iter()
+        .map(|pattern| {
+            find_palindrome(&pattern.cols, should_smudge)
+                + find_palindrome(&pattern.rows, should_smudge) * 100
+        })
+        .sum::<usize>()
+}
+
+/// Summary of the reflection lines of all patterns.
+pub fn part_1(input: &[u8]) -> Result<usize, ParseError> {
+    solve(input, false)
+}
+
+/// Summary of the reflection lines after fixing the smudge on each mirror.
+pub fn part_2(input: &[u8]) -> Result<usize, ParseError> {
+    solve(input, true)
+}
+
+/// The [`Solution`] of this day.
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed<'a> = Vec<Pattern>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 13;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-13/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(patterns: &Self::Parsed<'_>) -> Self::Part1 {
+        summarize(patterns, false)
+    }
+
+    fn part_2(patterns: &Self::Parsed<'_>) -> Self::Part2 {
+        summarize(patterns, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(405));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(400));
+    }
+}
@@ -0,0 +1,198 @@
+//! [Day 14: Parabolic Reflector Dish](https://adventofcode.com/2023/day/14)
+
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
+
+use itertools::izip;
+
+use crate::{
+    parse::Parser,
+    viz::{self, Frame, Sink, VizError},
+    Grid, ParseError, Solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rock {
+    Round,
+    Cube,
+}
+
+fn parse(input: &[u8]) -> Result<Grid<Option<Rock>>, ParseError> {
+    let parser = Parser::new(Day14::DAY, input);
+    Grid::parse_with(&parser, input, b".O#", |c| match c {
+        b'O' => Some(Rock::Round),
+        b'#' => Some(Rock::Cube),
+        _ => None,
+    })
+}
+
+/// Total load on the north support beams after tilting north.
+pub fn part_1(input: &[u8]) -> Result<usize, ParseError> {
+    parse(input).map(|map| north_load(&map))
+}
+
+/// Total load on the north support beams after 1 000 000 000 spin cycles.
+pub fn part_2(input: &[u8]) -> Result<usize, ParseError> {
+    parse(input).map(|map| spin_cycle_load(&map, |_| ()))
+}
+
+fn north_load(map: &Grid<Option<Rock>>) -> usize {
+    let rows = map.height();
+
+    map.columns()
+        .map(|col| {
+            izip!(col, (1..=rows).rev())
+                .filter_map(|(c, i)| Some((c.as_ref()?, i)))
+                .scan(rows + 1, |last_wall_pos, (rock, i)| match rock {
+                    Rock::Round => {
+                        *last_wall_pos -= 1;
+                        Some(*last_wall_pos)
+                    }
+                    Rock::Cube => {
+                        *last_wall_pos = i;
+                        Some(0)
+                    }
+                })
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+/// Rotates the platform clockwise and tilts it east.
+fn turn(map: &mut Grid<Option<Rock>>) {
+    *map = map.rotate_clockwise();
+
+    for row in map.rows_mut() {
+        let mut last_wall_pos = row.len();
+        for x in (0..row.len()).rev() {
+            match row[x] {
+                None => (),
+                Some(Rock::Round) => {
+                    last_wall_pos -= 1;
+                    row[x] = None;
+                    row[last_wall_pos] = Some(Rock::Round);
+                }
+                Some(Rock::Cube) => {
+                    last_wall_pos = x;
+                }
+            }
+        }
+    }
+}
+
+fn cycle(map: &mut Grid<Option<Rock>>) {
+    for _ in 0..4 {
+        turn(map);
+    }
+}
+
+fn get_load_p2(map: &Grid<Option<Rock>>) -> usize {
+    izip!(map.rows(), (1..=map.height()).rev())
+        .map(|(col, value)| {
+            value
+                * col
+                    .iter()
+                    .filter(|c| matches!(c, Some(Rock::Round)))
+                    .count()
+        })
+        .sum()
+}
+
+/// Load on the north beams after all the spin cycles, `observe` sees the
+/// map after each cycle until they repeat.
+fn spin_cycle_load(
+    map: &Grid<Option<Rock>>,
+    mut observe: impl FnMut(&Grid<Option<Rock>>),
+) -> usize {
+    let mut cache: Vec<(u64, Grid<Option<Rock>>)> = Vec::new();
+    let mut map = map.clone();
+
+    for it in 0..1e9 as _ {
+        cycle(&mut map);
+        observe(&map);
+
+        let map_hash = BuildHasherDefault::<fnv::FnvHasher>::default().hash_one(&map);
+
+        if let Some(cycle_start) = cache.iter().position(|(h, m)| h == &map_hash && m == &map) {
+            let cycle_len = it - cycle_start;
+            let remaining_steps = 1e9 as usize - it - 1;
+            let remainder = remaining_steps % cycle_len;
+
+            return get_load_p2(&cache[cycle_start + remainder].1);
+        }
+
+        cache.push((map_hash, map.clone()));
+    }
+    get_load_p2(&map)
+}
+
+/// Draws the rocks after every spin cycle until the cycles repeat, see
+/// [`viz`].
+pub fn visualize(input: &[u8], sink: &mut dyn Sink) -> Result<(), VizError> {
+    let frame = |map: &Grid<Option<Rock>>| {
+        Frame::new(map, |rock| match rock {
+            Some(Rock::Round) => viz::PATH,
+            Some(Rock::Cube) => viz::FEATURE,
+            None => viz::BACKGROUND,
+        })
+    };
+    let map = parse(input)?;
+    sink.frame(&frame(&map))?;
+    let mut written = Ok(());
+    spin_cycle_load(&map, |map| {
+        if written.is_ok() {
+            written = sink.frame(&frame(map));
+        }
+    });
+    Ok(written?)
+}
+
+/// The [`Solution`] of this day.
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed<'a> = Grid<Option<Rock>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 14;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-14/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(map: &Self::Parsed<'_>) -> Self::Part1 {
+        north_load(map)
+    }
+
+    fn part_2(map: &Self::Parsed<'_>) -> Self::Part2 {
+        spin_cycle_load(map, |_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(136));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(64));
+    }
+}
@@ -0,0 +1,181 @@
+//! [Day 15: Lens Library](https://adventofcode.com/2023/day/15)
+
+use std::io;
+
+use bstr::ByteSlice;
+
+use crate::{
+    parse::Parser,
+    stream::{self, StreamError},
+    ParseError, Solution,
+};
+
+/// The HASH algorithm, i.e. the box a label belongs in.
+pub fn hash_box(input: &[u8]) -> u8 {
+    input
+        .iter()
+        .fold(0, |acc, &c| acc.overflowing_add(c).0.overflowing_mul(17).0)
+}
+
+/// A step of the initialization sequence.
+#[derive(Debug, Clone, Copy)]
+pub struct Step<'a> {
+    raw: &'a [u8],
+    operation: Operation<'a>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operation<'a> {
+    /// Puts a lens of the focal length into the box of the label.
+    Insert(&'a [u8], u8),
+    /// Removes the lens with the label from its box.
+    Remove(&'a [u8]),
+}
+
+fn parse(input: &[u8]) -> Result<Vec<Step<'_>>, ParseError> {
+    let parser = Parser::new(Day15::DAY, input);
+    input
+        .split_str(",")
+        .map(|raw| parse_step(&parser, raw))
+        .collect()
+}
+
+fn parse_step<'a>(parser: &Parser, raw: &'a [u8]) -> Result<Step<'a>, ParseError> {
+    let operation = match raw {
+        [box_name @ .., b'=', value @ b'0'..=b'9'] => Operation::Insert(box_name, value - b'0'),
+        [box_name @ .., b'-'] => Operation::Remove(box_name),
+        _ => {
+            return Err(parser.error(raw, "a step like `label=4` or `label-`"));
+        }
+    };
+    Ok(Step { raw, operation })
+}
+
+/// Sum of the HASH of every step of the initialization sequence.
+pub fn part_1(input: &[u8]) -> Result<u64, ParseError> {
+    parse(input).map(|steps| hash_sum(&steps))
+}
+
+/// Focusing power of the lens configuration after the HASHMAP procedure.
+pub fn part_2(input: &[u8]) -> Result<u64, ParseError> {
+    parse(input).map(|steps| focusing_power(&steps))
+}
+
+/// Solves both parts reading one step of `reader` at a time, see
+/// [`stream`](crate::stream). Only the lenses in the boxes are kept.
+pub fn solve_stream(reader: impl io::BufRead) -> Result<(u64, u64), StreamError> {
+    let mut part_1 = 0;
+    let mut boxes = Boxes::new();
+    stream::for_each_record(Day15::DAY, reader, b',', |parser, raw| {
+        let step = parse_step(parser, raw)?;
+        part_1 += u64::from(hash_box(step.raw));
+        boxes.apply(step.operation, <[u8]>::to_vec);
+        Ok(())
+    })?;
+    Ok((part_1, boxes.focusing_power()))
+}
+
+fn hash_sum(steps: &[Step]) -> u64 {
+    steps.iter().map(|step| u64::from(hash_box(step.raw))).sum()
+}
+
+fn focusing_power(steps: &[Step]) -> u64 {
+    let mut boxes = Boxes::new();
+    for step in steps {
+        boxes.apply(step.operation, |label| label);
+    }
+    boxes.focusing_power()
+}
+
+/// The lenses in each box with their labels, borrowed from the input or
+/// owned when streaming.
+struct Boxes<L> {
+    // Boxes hold only a handful of lenses, so a linear scan beats any map.
+    lenses: Vec<Vec<(L, u8)>>,
+}
+
+impl<L: AsRef<[u8]>> Boxes<L> {
+    fn new() -> Self {
+        Self {
+            lenses: (0..256).map(|_| Vec::new()).collect(),
+        }
+    }
+
+    /// Performs the `operation`, turning its label into an `L` with
+    /// `to_label` when inserting a new lens.
+    fn apply<'a>(&mut self, operation: Operation<'a>, to_label: impl FnOnce(&'a [u8]) -> L) {
+        match operation {
+            Operation::Insert(label, focal_length) => {
+                let lenses = &mut self.lenses[hash_box(label) as usize];
+                match lenses.iter_mut().find(|(name, _)| name.as_ref() == label) {
+                    Some((_, value)) => *value = focal_length,
+                    None => lenses.push((to_label(label), focal_length)),
+                }
+            }
+            Operation::Remove(label) => {
+                let lenses = &mut self.lenses[hash_box(label) as usize];
+                if let Some(i) = lenses.iter().position(|(name, _)| name.as_ref() == label) {
+                    lenses.remove(i);
+                }
+            }
+        }
+    }
+
+    fn focusing_power(&self) -> u64 {
+        self.lenses
+            .iter()
+            .zip(1..)
+            .map(|(lenses, box_num)| {
+                box_num
+                    * lenses
+                        .iter()
+                        .zip(1..)
+                        .map(|((_, value), slot)| u64::from(*value) * slot)
+                        .sum::<u64>()
+            })
+            .sum()
+    }
+}
+
+/// The [`Solution`] of this day.
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed<'a> = Vec<Step<'a>>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    const DAY: u8 = 15;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-15/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(steps: &Self::Parsed<'_>) -> Self::Part1 {
+        hash_sum(steps)
+    }
+
+    fn part_2(steps: &Self::Parsed<'_>) -> Self::Part2 {
+        focusing_power(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(hash_box(b"HASH"), 52);
+        assert_eq!(part_1(EXAMPLE), Ok(1320));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(145));
+    }
+}
@@ -0,0 +1,229 @@
+//! [Day 16: The Floor Will Be Lava](https://adventofcode.com/2023/day/16)
+
+use std::mem;
+
+use itertools::Itertools;
+use rayon::prelude::*;
+
+use crate::{
+    parse::Parser,
+    viz::{self, Frame, Sink, VizError},
+    Grid, ParseError, Solution,
+};
+
+/// Heading of a beam.
+#[derive(Debug, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Visited {
+    up: bool,
+    down: bool,
+    left: bool,
+    right: bool,
+}
+
+impl Visited {
+    fn visit_dir(&mut self, dir: Direction) -> bool {
+        let field = match dir {
+            Direction::Up => &mut self.up,
+            Direction::Down => &mut self.down,
+            Direction::Left => &mut self.left,
+            Direction::Right => &mut self.right,
+        };
+        mem::replace(field, true)
+    }
+
+    fn is_energized(&self) -> bool {
+        self.up || self.down || self.left || self.right
+    }
+}
+
+fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(&Parser::new(Day16::DAY, input), input, br".|-/\")
+}
+
+/// Number of tiles energized by a beam entering at `start`.
+pub fn calc_energy(map: &[u8], start: (usize, usize, Direction)) -> Result<u32, ParseError> {
+    parse(map).map(|map| energize(&map, start))
+}
+
+fn energize(map: &Grid<u8>, start: (usize, usize, Direction)) -> u32 {
+    let result = energized(map, start, |_| ());
+    result.cells().iter().filter(|v| v.is_energized()).count() as u32
+}
+
+/// Where the beam entering at `start` went, `observe` sees this after every
+/// tile the beam enters.
+fn energized(
+    map: &Grid<u8>,
+    start: (usize, usize, Direction),
+    mut observe: impl FnMut(&Grid<Visited>),
+) -> Grid<Visited> {
+    let (width, height) = (map.width(), map.height());
+    let mut cursors = vec![start];
+    let mut result = Grid::new(width, height, Visited::default());
+    while let Some((x, y, dir)) = cursors.pop() {
+        if result[(x, y)].visit_dir(dir) {
+            continue;
+        }
+        observe(&result);
+
+        match (map[(x, y)], dir) {
+            (b'.' | b'-', Direction::Right) | (b'/', Direction::Up) | (b'\\', Direction::Down)
+                if x < width - 1 =>
+            {
+                cursors.push((x + 1, y, Direction::Right));
+            }
+            (b'.' | b'-', Direction::Left) | (b'/', Direction::Down) | (b'\\', Direction::Up)
+                if x > 0 =>
+            {
+                cursors.push((x - 1, y, Direction::Left));
+            }
+            (b'.' | b'|', Direction::Down)
+            | (b'/', Direction::Left)
+            | (b'\\', Direction::Right)
+                if y < height - 1 =>
+            {
+                cursors.push((x, y + 1, Direction::Down));
+            }
+            (b'.' | b'|', Direction::Up) | (b'/', Direction::Right) | (b'\\', Direction::Left)
+                if y > 0 =>
+            {
+                cursors.push((x, y - 1, Direction::Up));
+            }
+            (b'-', Direction::Up | Direction::Down) => {
+                if x > 0 {
+                    cursors.push((x - 1, y, Direction::Left));
+                }
+                if x < width - 1 {
+                    cursors.push((x + 1, y, Direction::Right));
+                }
+            }
+            (b'|', Direction::Left | Direction::Right) => {
+                if y > 0 {
+                    cursors.push((x, y - 1, Direction::Up));
+                }
+                if y < height - 1 {
+                    cursors.push((x, y + 1, Direction::Down));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    result
+}
+
+/// Number of tiles energized by a beam entering at the top left heading
+/// right.
+pub fn part_1(input: &[u8]) -> Result<u32, ParseError> {
+    calc_energy(input, (0, 0, Direction::Right))
+}
+
+/// Most tiles energized by a beam entering from any edge tile.
+pub fn part_2(input: &[u8]) -> Result<u32, ParseError> {
+    parse(input).map(|map| most_energy(&map))
+}
+
+fn most_energy(map: &Grid<u8>) -> u32 {
+    let (width, height) = (map.width(), map.height());
+    (0..width)
+        .map(|x| (x, 0, Direction::Down))
+        .chain((0..height).map(|y| (0, y, Direction::Right)))
+        .chain((0..width).map(|x| (x, height - 1, Direction::Up)))
+        .chain((0..height).map(|y| (width - 1, y, Direction::Left)))
+        .collect_vec()
+        .into_par_iter()
+        .map(|start| energize(map, start))
+        .max()
+        .unwrap()
+}
+
+/// Draws the tiles energized by the beam of part 1 as it spreads, see
+/// [`viz`].
+pub fn visualize(input: &[u8], sink: &mut dyn Sink) -> Result<(), VizError> {
+    let map = parse(input)?;
+    let frame = |visited: &Grid<Visited>| {
+        let mut frame = Frame::new(&map, |&tile| {
+            if tile == b'.' {
+                viz::BACKGROUND
+            } else {
+                viz::FEATURE
+            }
+        });
+        let energized = map.positions().filter(|&p| visited[p].is_energized());
+        let (empty, devices): (Vec<_>, Vec<_>) = energized.partition(|&p| map[p] == b'.');
+        frame.paint(empty, viz::REACHED).paint(devices, viz::PATH);
+        frame
+    };
+
+    // A frame each time the beam could have crossed the map.
+    let mut steps = 0_usize;
+    let mut written = Ok(());
+    let visited = energized(&map, (0, 0, Direction::Right), |visited| {
+        steps += 1;
+        if written.is_ok() && steps.is_multiple_of(map.width()) {
+            written = sink.frame(&frame(visited));
+        }
+    });
+    written?;
+    sink.frame(&frame(&visited))?;
+    Ok(())
+}
+
+/// The [`Solution`] of this day.
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed<'a> = Grid<u8>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const DAY: u8 = 16;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-16/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(map: &Self::Parsed<'_>) -> Self::Part1 {
+        energize(map, (0, 0, Direction::Right))
+    }
+
+    fn part_2(map: &Self::Parsed<'_>) -> Self::Part2 {
+        most_energy(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = br".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(46));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(51));
+    }
+}
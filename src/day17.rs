@@ -0,0 +1,191 @@
+//! [Day 17: Clumsy Crucible](https://adventofcode.com/2023/day/17)
+
+use itertools::Itertools;
+
+use crate::{
+    parse::Parser,
+    search::{self, Path},
+    viz::{self, Frame, Sink, VizError},
+    Grid, ParseError, Solution,
+};
+
+/// Axis the crucible moved along last, it has to turn onto the other one.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// Longest side of the map, which keeps the heat losses in a `u16`.
+const MAX_SIDE: usize = 1000;
+
+/// The heat loss of every block.
+fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    let parser = Parser::new(Day17::DAY, input);
+    let map = Grid::parse_with(&parser, input, b"0123456789", |c| c - b'0')?;
+    if map.width().max(map.height()) > MAX_SIDE {
+        return Err(parser.error(
+            input,
+            format!("a map of at most {MAX_SIDE} by {MAX_SIDE} blocks"),
+        ));
+    }
+    Ok(map)
+}
+
+/// Least heat loss from the top left to the bottom right block.
+pub fn part_1(input: &[u8]) -> Result<Option<u16>, ParseError> {
+    solve(input, false)
+}
+
+/// Least heat loss using an ultra crucible.
+pub fn part_2(input: &[u8]) -> Result<Option<u16>, ParseError> {
+    solve(input, true)
+}
+
+/// Least heat loss from the top left to the bottom right block, `ultra`
+/// selects the ultra crucible which moves 4 to 10 blocks in a line. `None` if
+/// the crucible can't get there.
+pub fn solve(input: &[u8], ultra: bool) -> Result<Option<u16>, ParseError> {
+    parse(input).map(|map| least_heat_loss(&map, ultra))
+}
+
+#[inline]
+fn least_heat_loss(map: &Grid<u8>, ultra: bool) -> Option<u16> {
+    best_path(map, ultra).map(|path| path.cost)
+}
+
+/// A crucible's position and the axis it last moved along.
+type State = (usize, usize, Option<Axis>);
+
+/// Path with the least heat loss to the bottom right block, through the
+/// blocks where the crucible turns.
+fn best_path(map: &Grid<u8>, ultra: bool) -> Option<Path<State, u16>> {
+    let (min_run, max_run) = if ultra { (4, 10) } else { (1, 3) };
+    let goal = (map.width() - 1, map.height() - 1);
+
+    // Each move turns and goes straight for `min_run..=max_run` blocks, the
+    // start has no axis yet and may go either way.
+    let successors = |&(x, y, axis): &State| {
+        let mut next = Vec::with_capacity(2 * (max_run - min_run + 1));
+        for (new_axis, offsets) in [
+            (Axis::Horizontal, [(1, 0), (-1, 0)]),
+            (Axis::Vertical, [(0, 1), (0, -1)]),
+        ] {
+            if axis == Some(new_axis) {
+                continue;
+            }
+            for offset in offsets {
+                let mut position = (x, y);
+                let mut heat_loss = 0;
+                for run in 1..=max_run {
+                    let Some(step) = map.step(position, offset) else {
+                        break;
+                    };
+                    position = step;
+                    heat_loss += u16::from(map[position]);
+                    if run >= min_run {
+                        next.push(((position.0, position.1, Some(new_axis)), heat_loss));
+                    }
+                }
+            }
+        }
+        next
+    };
+    // Every block loses at least as much heat as the coolest one, which may
+    // lose none.
+    let min_loss = u16::from(map.cells().iter().copied().min().unwrap_or(0));
+    let heuristic =
+        |&(x, y, _): &State| (goal.0.abs_diff(x) + goal.1.abs_diff(y)) as u16 * min_loss;
+
+    search::astar((0, 0, None), successors, heuristic, |&(x, y, _)| {
+        (x, y) == goal
+    })
+    .path
+}
+
+/// Draws the heat loss of the blocks, then the paths of both crucibles one
+/// straight run at a time, see [`viz`].
+pub fn visualize(input: &[u8], sink: &mut dyn Sink) -> Result<(), VizError> {
+    let map = parse(input)?;
+    // Brighter blocks lose more heat.
+    let heat = Frame::new(&map, |&loss| {
+        [15 + 18 * loss, 15 + 18 * loss, 25 + 20 * loss]
+    });
+    sink.frame(&heat)?;
+
+    for ultra in [false, true] {
+        let Some(path) = best_path(&map, ultra) else {
+            continue;
+        };
+        let mut frame = heat.clone();
+        for (&(x0, y0, _), &(x1, y1, _)) in path.states.iter().tuple_windows() {
+            let run = (x0.min(x1)..=x0.max(x1))
+                .flat_map(|x| (y0.min(y1)..=y0.max(y1)).map(move |y| (x, y)));
+            sink.frame(frame.paint(run, viz::PATH))?;
+        }
+    }
+    Ok(())
+}
+
+/// The [`Solution`] of this day.
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed<'a> = Grid<u8>;
+    type Part1 = Option<u16>;
+    type Part2 = Option<u16>;
+
+    const DAY: u8 = 17;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-17/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(map: &Self::Parsed<'_>) -> Self::Part1 {
+        least_heat_loss(map, false)
+    }
+
+    fn part_2(map: &Self::Parsed<'_>) -> Self::Part2 {
+        least_heat_loss(map, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    const EXAMPLE2: &[u8] = b"111111111111
+999999999991
+999999999991
+999999999991
+999999999991";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(Some(102)));
+        // Blocks that lose no heat.
+        assert_eq!(part_1(b"0000\n0991\n0000"), Ok(Some(0)));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE2), Ok(Some(71)));
+        assert_eq!(part_2(EXAMPLE), Ok(Some(94)));
+    }
+}
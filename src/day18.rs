@@ -0,0 +1,334 @@
+//! [Day 18: Lavaduct Lagoon](https://adventofcode.com/2023/day/18)
+
+use std::io;
+
+use bstr::ByteSlice;
+
+use crate::{
+    math,
+    parse::Parser,
+    stream::{self, StreamError},
+    ParseError, Solution,
+};
+
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+/// One instruction of the dig plan.
+#[derive(Debug, Clone, Copy)]
+pub struct Dig {
+    direction: Direction,
+    steps: i64,
+}
+
+/// Longest trench, which keeps twice the area of the lagoon in a `u64`.
+const MAX_LENGTH: i64 = 1 << 30;
+
+/// A trench dug from the origin so far.
+#[derive(Debug, Clone, Copy, Default)]
+struct Trench {
+    end: [i64; 2],
+    length: i64,
+    /// Twice the signed area between the trench and the origin, the sum of
+    /// the shoelace formula so far.
+    double_area: i64,
+}
+
+impl Trench {
+    /// The trench dug on by `dig`, `None` if that makes it longer than
+    /// [`MAX_LENGTH`].
+    fn dig(self, dig: &Dig) -> Option<Self> {
+        let length = self.length + dig.steps;
+        if length > MAX_LENGTH {
+            return None;
+        }
+        let [x0, y0] = self.end;
+        let [x1, y1] = match dig.direction {
+            Direction::Right => [x0 + dig.steps, y0],
+            Direction::Down => [x0, y0 + dig.steps],
+            Direction::Left => [x0 - dig.steps, y0],
+            Direction::Up => [x0, y0 - dig.steps],
+        };
+        Some(Self {
+            end: [x1, y1],
+            length,
+            double_area: self
+                .double_area
+                .checked_add(math::shoelace_step(self.end, [x1, y1])?)?,
+        })
+    }
+
+    /// Cubic meters of lava the lagoon holds, once the trench is back at the
+    /// origin.
+    fn volume(&self) -> u64 {
+        let boundary = self.length as u64;
+        math::interior_points(self.double_area.unsigned_abs(), boundary) + boundary
+    }
+}
+
+/// Parses an instruction of the dig plan, both as written and as hidden in
+/// the colour code.
+fn parse_dig(parser: &Parser, line: &[u8]) -> Result<(Dig, Dig), ParseError> {
+    let (direction, rest) = parser.split_once(line, " ")?;
+    let (steps, colour) = parser.split_once(rest, " ")?;
+    let direction = match direction {
+        b"R" => Direction::Right,
+        b"D" => Direction::Down,
+        b"L" => Direction::Left,
+        b"U" => Direction::Up,
+        _ => return Err(parser.error(direction, "`R`, `D`, `L` or `U`")),
+    };
+    let plain = Dig {
+        direction,
+        steps: i64::from(parser.number::<u32>(steps)?),
+    };
+
+    let colour = parser.strip_suffix(parser.strip_prefix(colour, "(#")?, ")")?;
+    let (Some(steps), Some(direction)) = (
+        colour.get(..5).and_then(|hex| {
+            let hex = std::str::from_utf8(hex).ok()?;
+            i64::from_str_radix(hex, 16).ok()
+        }),
+        colour.get(5..),
+    ) else {
+        return Err(parser.error(colour, "a colour code of 6 hex digits"));
+    };
+    let direction = match direction {
+        b"0" => Direction::Right,
+        b"1" => Direction::Down,
+        b"2" => Direction::Left,
+        b"3" => Direction::Up,
+        _ => return Err(parser.error(direction, "a direction between `0` and `3`")),
+    };
+
+    Ok((plain, Dig { direction, steps }))
+}
+
+/// Digs both trenches on, with the error for `line` if one gets too long.
+fn dig_both(
+    parser: &Parser,
+    line: &[u8],
+    trenches: [Trench; 2],
+    (plain, hidden): &(Dig, Dig),
+) -> Result<[Trench; 2], ParseError> {
+    match (trenches[0].dig(plain), trenches[1].dig(hidden)) {
+        (Some(plain), Some(hidden)) => Ok([plain, hidden]),
+        _ => Err(parser.error(line, "a trench shorter than 2^30 meters")),
+    }
+}
+
+/// Both trenches have to be closed for the lagoon to hold anything.
+fn is_closed(trenches: [Trench; 2]) -> bool {
+    trenches.iter().all(|trench| trench.end == [0, 0])
+}
+
+const NOT_CLOSED: &str = "a trench ending where it starts";
+
+/// The instructions of the dig plan, both as written and as hidden in the
+/// colour codes.
+fn parse(input: &[u8]) -> Result<Vec<(Dig, Dig)>, ParseError> {
+    let parser = Parser::new(Day18::DAY, input);
+    let mut trenches = [Trench::default(); 2];
+    let plan = input
+        .lines()
+        .map(|line| {
+            let dig = parse_dig(&parser, line)?;
+            trenches = dig_both(&parser, line, trenches, &dig)?;
+            Ok(dig)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if !is_closed(trenches) {
+        return Err(parser.error(parser.end(), NOT_CLOSED));
+    }
+    Ok(plan)
+}
+
+/// Cubic meters of lava the dug out lagoon can hold.
+pub fn part_1(input: &[u8]) -> Result<u64, ParseError> {
+    parse(input).map(|plan| lagoon_volume(plan.iter().map(|(plain, _)| plain)))
+}
+
+/// Cubic meters of lava the lagoon holds when following the instructions
+/// hidden in the colour codes.
+pub fn part_2(input: &[u8]) -> Result<u64, ParseError> {
+    parse(input).map(|plan| lagoon_volume(plan.iter().map(|(_, hidden)| hidden)))
+}
+
+/// Solves both parts reading one instruction of `reader` at a time, see
+/// [`stream`](crate::stream).
+pub fn solve_stream(reader: impl io::BufRead) -> Result<(u64, u64), StreamError> {
+    let mut trenches = [Trench::default(); 2];
+    let end = stream::for_each_record(Day18::DAY, reader, b'\n', |parser, line| {
+        let dig = parse_dig(parser, line)?;
+        trenches = dig_both(parser, line, trenches, &dig)?;
+        Ok(())
+    })?;
+    if !is_closed(trenches) {
+        return Err(end.error(Day18::DAY, NOT_CLOSED).into());
+    }
+    Ok((trenches[0].volume(), trenches[1].volume()))
+}
+
+fn lagoon_volume<'a>(plan: impl Iterator<Item = &'a Dig>) -> u64 {
+    plan.fold(Trench::default(), |trench, dig| {
+        trench.dig(dig).expect("the trench is checked when parsing")
+    })
+    .volume()
+}
+
+/// The [`Solution`] of this day.
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed<'a> = Vec<(Dig, Dig)>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    const DAY: u8 = 18;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-18/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(plan: &Self::Parsed<'_>) -> Self::Part1 {
+        lagoon_volume(plan.iter().map(|(plain, _)| plain))
+    }
+
+    fn part_2(plan: &Self::Parsed<'_>) -> Self::Part2 {
+        lagoon_volume(plan.iter().map(|(_, hidden)| hidden))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{search, Grid};
+
+    const EXAMPLE: &[u8] = b"R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(62));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(952_408_144_115));
+    }
+
+    /// Digs the trench on a grid and flood fills the outside.
+    fn lagoon_volume_naive(plan: &[Dig]) -> u64 {
+        let mut trench = vec![(0i64, 0i64)];
+        for dig in plan {
+            let (dx, dy) = match dig.direction {
+                Direction::Right => (1, 0),
+                Direction::Down => (0, 1),
+                Direction::Left => (-1, 0),
+                Direction::Up => (0, -1),
+            };
+            for _ in 0..dig.steps {
+                let (x, y) = *trench.last().unwrap();
+                trench.push((x + dx, y + dy));
+            }
+        }
+        // A margin of one, so the outside is connected.
+        let min_x = trench.iter().map(|p| p.0).min().unwrap() - 1;
+        let min_y = trench.iter().map(|p| p.1).min().unwrap() - 1;
+        let width = (trench.iter().map(|p| p.0).max().unwrap() - min_x + 2) as usize;
+        let height = (trench.iter().map(|p| p.1).max().unwrap() - min_y + 2) as usize;
+        let mut grid = Grid::new(width, height, false);
+        for &(x, y) in &trench {
+            grid[((x - min_x) as usize, (y - min_y) as usize)] = true;
+        }
+
+        let outside = search::bfs_distances(
+            (0, 0),
+            |&(x, y)| {
+                grid.neighbors_4(x, y)
+                    .filter(|&p| !grid[p])
+                    .collect::<Vec<_>>()
+            },
+            usize::MAX,
+        );
+        (width * height - outside.distances.len()) as u64
+    }
+
+    /// Plans tracing a histogram: columns of random widths and heights on a
+    /// common base, walked in either direction and rotated.
+    fn plan() -> impl Strategy<Value = Vec<Dig>> {
+        (
+            prop::collection::vec((1..6i64, 1..8i64), 1..8),
+            any::<bool>(),
+            0..4usize,
+        )
+            .prop_map(|(columns, reverse, rotation)| {
+                use Direction::*;
+
+                let mut plan = Vec::new();
+                let mut dig = |direction, steps: i64| {
+                    let direction = if steps < 0 {
+                        [Right, Down, Left, Up][(direction as usize + 2) % 4]
+                    } else {
+                        direction
+                    };
+                    if steps != 0 {
+                        plan.push(Dig {
+                            direction,
+                            steps: steps.abs(),
+                        });
+                    }
+                };
+                let mut level = 0;
+                for &(width, height) in &columns {
+                    dig(Up, height - level);
+                    dig(Right, width);
+                    level = height;
+                }
+                dig(Down, level);
+                dig(Left, columns.iter().map(|c| c.0).sum());
+
+                if reverse {
+                    plan.reverse();
+                    for dig in &mut plan {
+                        dig.direction = [Left, Up, Right, Down][dig.direction as usize];
+                    }
+                }
+                for dig in &mut plan {
+                    dig.direction =
+                        [Right, Down, Left, Up][(dig.direction as usize + rotation) % 4];
+                }
+                plan
+            })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2000))]
+
+        #[test]
+        fn test_lagoon_volume(plan in plan()) {
+            prop_assert_eq!(lagoon_volume(plan.iter()), lagoon_volume_naive(&plan));
+        }
+    }
+}
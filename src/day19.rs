@@ -0,0 +1,392 @@
+//! [Day 19: Aplenty](https://adventofcode.com/2023/day/19)
+
+use bstr::ByteSlice;
+use fnv::FnvHashMap;
+
+use crate::{parse::Parser, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Gt,
+    Lt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Target {
+    Reject,
+    Accept,
+    Rule([u8; 3]),
+}
+
+#[derive(Debug, Clone)]
+struct Ruleset {
+    rules: Vec<Rule>,
+    default_target: Target,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rule {
+    attr: u8,
+    threshold: u16,
+    target: Target,
+    op: Op,
+}
+
+#[derive(Clone, Copy)]
+struct ValueRange {
+    min: u16,
+    max: u16,
+}
+
+impl ValueRange {
+    fn new(min: u16, max: u16) -> Self {
+        Self { min, max }
+    }
+
+    fn combination_count(&self) -> u64 {
+        u64::from(self.max + 1 - self.min)
+    }
+
+    /// The values failing and passing the comparison with `threshold`.
+    fn split(&self, threshold: u16, op: Op) -> (Self, Self) {
+        match op {
+            Op::Gt => (
+                Self::new(self.min, threshold.min(self.max)),
+                Self::new((threshold + 1).max(self.min), self.max),
+            ),
+            Op::Lt => (
+                Self::new(threshold.max(self.min), self.max),
+                Self::new(self.min, (threshold - 1).min(self.max)),
+            ),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.min > self.max
+    }
+}
+
+/// The workflows and the ratings of the parts.
+#[derive(Debug, Clone)]
+pub struct System {
+    workflows: FnvHashMap<[u8; 3], Ruleset>,
+    parts: Vec<[u16; 4]>,
+}
+
+fn pad_rule(parser: &Parser, rule: &[u8]) -> Result<[u8; 3], ParseError> {
+    if rule.is_empty() || rule.len() > 3 {
+        return Err(parser.error(rule, "a workflow name of 1 to 3 letters"));
+    }
+    let mut buf = [b' '; 3];
+
+    buf[3 - rule.len()..].copy_from_slice(rule);
+    Ok(buf)
+}
+
+fn parse_rating(parser: &Parser, rating: &[u8]) -> Result<u16, ParseError> {
+    match parser.number(rating) {
+        Ok(rating @ 1..=4000) => Ok(rating),
+        _ => Err(parser.error(rating, "a rating between 1 and 4000")),
+    }
+}
+
+fn parse_target(parser: &Parser, target: &[u8]) -> Result<Target, ParseError> {
+    Ok(match target {
+        b"R" => Target::Reject,
+        b"A" => Target::Accept,
+        target => Target::Rule(pad_rule(parser, target)?),
+    })
+}
+
+fn parse_rules(
+    parser: &Parser,
+    rule_desc: &[u8],
+) -> Result<FnvHashMap<[u8; 3], Ruleset>, ParseError> {
+    let mut rules = FnvHashMap::default();
+    let mut targets = Vec::new();
+    for line in rule_desc.lines() {
+        let line = parser.strip_suffix(line, "}")?;
+        let (name, instructions) = parser.split_once(line, "{")?;
+        let name = pad_rule(parser, name)?;
+
+        let (instructions, default_target) = match instructions.rsplit_once_str(",") {
+            Some((instructions, default_target)) => (Some(instructions), default_target),
+            None => (None, instructions),
+        };
+        targets.push((name, default_target));
+        let default_target = parse_target(parser, default_target)?;
+
+        let mut rs = Ruleset {
+            rules: Vec::new(),
+            default_target,
+        };
+        for ins in instructions.into_iter().flat_map(|i| i.split_str(",")) {
+            let (cond, target) = parser.split_once(ins, ":")?;
+            targets.push((name, target));
+
+            let attr = match cond.first() {
+                Some(b'x') => 0,
+                Some(b'm') => 1,
+                Some(b'a') => 2,
+                Some(b's') => 3,
+                _ => return Err(parser.error(&cond[..cond.len().min(1)], "`x`, `m`, `a` or `s`")),
+            };
+
+            let op = match cond.get(1) {
+                Some(b'<') => Op::Lt,
+                Some(b'>') => Op::Gt,
+                _ => return Err(parser.error(&cond[1..cond.len().min(2)], "`<` or `>`")),
+            };
+
+            rs.rules.push(Rule {
+                attr,
+                threshold: parse_rating(parser, &cond[2..])?,
+                target: parse_target(parser, target)?,
+                op,
+            });
+        }
+
+        rules.insert(name, rs);
+    }
+
+    if !rules.contains_key(b" in") {
+        return Err(parser.error(&rule_desc[..0], "a workflow named `in`"));
+    }
+    let mut edges = Vec::new();
+    for (from, target) in targets {
+        if let Target::Rule(name) = parse_target(parser, target)? {
+            if !rules.contains_key(&name) {
+                return Err(parser.error(target, "the name of a workflow"));
+            }
+            edges.push((from, name, target));
+        }
+    }
+
+    // Sorting a part has to end, so no workflow may lead back to itself:
+    // removing workflows nothing leads to has to remove all of them.
+    let mut incoming = FnvHashMap::<_, usize>::default();
+    let mut outgoing = FnvHashMap::<_, Vec<_>>::default();
+    for &(from, to, _) in &edges {
+        *incoming.entry(to).or_default() += 1;
+        outgoing.entry(from).or_default().push(to);
+    }
+    let mut sources = rules
+        .keys()
+        .filter(|name| !incoming.contains_key(*name))
+        .copied()
+        .collect::<Vec<_>>();
+    while let Some(name) = sources.pop() {
+        for to in outgoing.remove(&name).unwrap_or_default() {
+            let count = incoming.get_mut(&to).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                incoming.remove(&to);
+                sources.push(to);
+            }
+        }
+    }
+    if let Some(&(_, _, target)) = edges
+        .iter()
+        .find(|(from, to, _)| incoming.contains_key(from) && incoming.contains_key(to))
+    {
+        return Err(parser.error(target, "a workflow that doesn't lead back here"));
+    }
+    Ok(rules)
+}
+
+fn parse(input: &[u8]) -> Result<System, ParseError> {
+    let parser = Parser::new(Day19::DAY, input);
+    let (rules, parts) = parser.split_once(input, "\n\n")?;
+    let workflows = parse_rules(&parser, rules)?;
+    let parts = parts
+        .lines()
+        .map(|line| {
+            let mut ratings = parser
+                .strip_suffix(parser.strip_prefix(line, "{")?, "}")?
+                .split_str(",");
+            let mut xmas = [0; 4];
+            for (rating, prefix) in xmas.iter_mut().zip(["x=", "m=", "a=", "s="]) {
+                let part = ratings.next().unwrap_or(&line[line.len()..]);
+                *rating = parse_rating(&parser, parser.strip_prefix(part, prefix)?)?;
+            }
+            if let Some(extra) = ratings.next() {
+                return Err(parser.error(extra, "`}`"));
+            }
+            Ok(xmas)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(System { workflows, parts })
+}
+
+/// Sum of the ratings of all accepted parts.
+pub fn part_1(input: &[u8]) -> Result<u32, ParseError> {
+    parse(input).map(|system| accepted_ratings(&system))
+}
+
+/// Number of distinct rating combinations accepted by the workflows.
+pub fn part_2(input: &[u8]) -> Result<u64, ParseError> {
+    parse(input).map(|system| accepted_combinations(&system.workflows))
+}
+
+fn accepted_ratings(system: &System) -> u32 {
+    let rules = &system.workflows;
+    let mut valid = 0;
+    'nextpart: for &xmas in &system.parts {
+        let mut cursor = &rules[b" in"];
+
+        'nextrule: loop {
+            for rule in &cursor.rules {
+                let rule_matches = match rule.op {
+                    Op::Gt => xmas[rule.attr as usize] > rule.threshold,
+                    Op::Lt => xmas[rule.attr as usize] < rule.threshold,
+                };
+                if rule_matches {
+                    match rule.target {
+                        Target::Reject => continue 'nextpart,
+                        Target::Accept => {
+                            valid += u32::from(xmas.iter().sum::<u16>());
+                            continue 'nextpart;
+                        }
+                        Target::Rule(target) => {
+                            cursor = &rules[&target];
+                            continue 'nextrule;
+                        }
+                    }
+                }
+            }
+            match cursor.default_target {
+                Target::Reject => continue 'nextpart,
+                Target::Accept => {
+                    valid += u32::from(xmas.iter().sum::<u16>());
+                    continue 'nextpart;
+                }
+                Target::Rule(target) => {
+                    cursor = &rules[&target];
+                    continue 'nextrule;
+                }
+            }
+        }
+    }
+
+    valid
+}
+
+fn accepted_combinations(rules: &FnvHashMap<[u8; 3], Ruleset>) -> u64 {
+    let mut queue = vec![(*b" in", [
+        ValueRange::new(1, 4000),
+        ValueRange::new(1, 4000),
+        ValueRange::new(1, 4000),
+        ValueRange::new(1, 4000),
+    ])];
+    let mut out = 0;
+    'workflows: while let Some((id, bounds)) = queue.pop() {
+        let cursor = &rules[&id];
+        let mut bounds = bounds;
+
+        for rule in &cursor.rules {
+            let mut rule_match_bounds = bounds;
+            (
+                bounds[rule.attr as usize],
+                rule_match_bounds[rule.attr as usize],
+            ) = bounds[rule.attr as usize].split(rule.threshold, rule.op);
+
+            if !rule_match_bounds[rule.attr as usize].is_empty() {
+                match rule.target {
+                    Target::Reject => (),
+                    Target::Accept => {
+                        out += rule_match_bounds
+                            .iter()
+                            .map(ValueRange::combination_count)
+                            .product::<u64>();
+                    }
+                    Target::Rule(target) => queue.push((target, rule_match_bounds)),
+                }
+            }
+
+            if bounds[rule.attr as usize].is_empty() {
+                continue 'workflows;
+            }
+        }
+
+        match cursor.default_target {
+            Target::Reject => continue,
+            Target::Accept => {
+                out += bounds
+                    .iter()
+                    .map(ValueRange::combination_count)
+                    .product::<u64>();
+            }
+            Target::Rule(target) => {
+                queue.push((target, bounds));
+            }
+        }
+    }
+
+    out
+}
+
+/// The [`Solution`] of this day.
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed<'a> = System;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    const DAY: u8 = 19;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-19/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(system: &Self::Parsed<'_>) -> Self::Part1 {
+        accepted_ratings(system)
+    }
+
+    fn part_2(system: &Self::Parsed<'_>) -> Self::Part2 {
+        accepted_combinations(&system.workflows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(19114));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(167_409_079_868_000));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = part_1(b"in{x<10:px,A}\npx{m>5:in,R}\n\n{x=1,m=2,a=3,s=4}").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 9));
+        assert_eq!(err.found, "`px`");
+    }
+}
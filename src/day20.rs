@@ -0,0 +1,255 @@
+//! [Day 20: Pulse Propagation](https://adventofcode.com/2023/day/20)
+
+use std::collections::VecDeque;
+
+use bstr::ByteSlice;
+use fnv::FnvHashMap;
+use itertools::Itertools;
+
+use crate::{parse::Parser, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pulse {
+    High,
+    Low,
+}
+
+#[derive(Debug, Clone)]
+enum ModuleKind<'a> {
+    FlipFlop { off: bool },
+    Conjunction { last_pulses: Vec<(&'a [u8], Pulse)> },
+}
+
+#[derive(Debug, Clone)]
+struct Module<'a> {
+    kind: ModuleKind<'a>,
+    outputs: Vec<&'a [u8]>,
+}
+
+/// The targets of the broadcaster and the other modules by name.
+#[derive(Debug, Clone)]
+pub struct Network<'a> {
+    broadcaster_targets: Vec<&'a [u8]>,
+    modules: FnvHashMap<&'a [u8], Module<'a>>,
+}
+
+fn parse(input: &[u8]) -> Result<Network<'_>, ParseError> {
+    let parser = Parser::new(Day20::DAY, input);
+    let broadcaster_targets = input
+        .lines()
+        .find_map(|line| {
+            line.strip_prefix(b"broadcaster -> ")
+                .map(|targets| targets.split_str(", ").collect_vec())
+        })
+        .ok_or_else(|| parser.error(parser.end(), "a `broadcaster -> ` line"))?;
+
+    let mut rules = input
+        .lines()
+        .filter(|line| !line.starts_with(b"broadcaster"))
+        .map(|line| {
+            let (name, outputs) = parser.split_once(line.get(1..).unwrap_or_default(), " -> ")?;
+            let outputs = outputs.split_str(", ").collect_vec();
+
+            let kind = match line[0] {
+                b'%' => ModuleKind::FlipFlop { off: true },
+                b'&' => ModuleKind::Conjunction {
+                    last_pulses: vec![],
+                },
+                _ => return Err(parser.error(&line[..1], "`%`, `&` or `broadcaster`")),
+            };
+
+            let module = Module { kind, outputs };
+            Ok((name, module))
+        })
+        .collect::<Result<FnvHashMap<_, _>, _>>()?;
+
+    let input_to_outputs = rules
+        .iter()
+        .map(|(name, module)| (*name, module.outputs.clone()))
+        .chain([(&b"broadcaster"[..], broadcaster_targets.clone())])
+        .collect_vec();
+
+    for (name, outputs) in input_to_outputs {
+        outputs
+            .iter()
+            .filter(|s| s != &b"output")
+            .for_each(|output| {
+                let Some(module) = rules.get_mut(output) else {
+                    return;
+                };
+                if let ModuleKind::Conjunction { last_pulses } = &mut module.kind {
+                    last_pulses.push((name, Pulse::Low));
+                }
+            });
+    }
+
+    Ok(Network {
+        broadcaster_targets,
+        modules: rules,
+    })
+}
+
+fn perform_tick<'a>(
+    queue: &mut VecDeque<(&'a [u8], &'a [u8], Pulse)>,
+    rules: &mut FnvHashMap<&'a [u8], Module<'a>>,
+    name: &'a [u8],
+    parent_name: &'a [u8],
+    pulse: Pulse,
+) {
+    let Some(module) = rules.get_mut(name) else {
+        return;
+    };
+    let pulse_type = match &mut module.kind {
+        ModuleKind::FlipFlop { off } => {
+            if pulse == Pulse::High {
+                return;
+            }
+            let pulse_type = if *off { Pulse::High } else { Pulse::Low };
+            *off = !*off;
+            pulse_type
+        }
+        ModuleKind::Conjunction { last_pulses } => {
+            last_pulses
+                .iter_mut()
+                .find(|(input, _pulse)| input == &parent_name)
+                .unwrap()
+                .1 = pulse;
+
+            let all_high = last_pulses.iter().all(|(_, pulse)| *pulse == Pulse::High);
+            if all_high {
+                Pulse::Low
+            } else {
+                Pulse::High
+            }
+        }
+    };
+    for output in &module.outputs {
+        queue.push_back((output, name, pulse_type));
+    }
+}
+
+/// Most pulses a single press of the button may send, modules can keep
+/// sending each other pulses forever.
+const MAX_PULSES: usize = 1 << 16;
+
+/// Most presses the counters feeding `rx` may take to fire.
+const MAX_PRESSES: u64 = 1 << 12;
+
+/// Product of the number of low and high pulses sent when the button is
+/// pushed 1000 times, `None` if a press sends pulses forever.
+pub fn part_1(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|network| pulse_product(&network))
+}
+
+/// Fewest button presses until a single low pulse is delivered to `rx`,
+/// `None` if `rx` isn't fed by counters of up to 12 bits.
+pub fn part_2(input: &[u8]) -> Result<Option<u64>, ParseError> {
+    parse(input).map(|network| presses_until_rx(&network))
+}
+
+fn pulse_product(network: &Network) -> Option<u64> {
+    let broadcaster_targets = &network.broadcaster_targets;
+    let mut rules = network.modules.clone();
+    let (mut output_low, mut output_high) = (1000, 0);
+    let mut queue = VecDeque::new();
+
+    for _ in 0..1000 {
+        for target in broadcaster_targets {
+            queue.push_back((*target, b"broadcaster".as_slice(), Pulse::Low));
+        }
+
+        let mut pulses = 0;
+        while let Some((name, parent_name, pulse)) = queue.pop_front() {
+            pulses += 1;
+            if pulses > MAX_PULSES {
+                return None;
+            }
+            match pulse {
+                Pulse::High => output_high += 1,
+                Pulse::Low => output_low += 1,
+            };
+            perform_tick(&mut queue, &mut rules, name, parent_name, pulse);
+        }
+    }
+
+    Some(output_low * output_high)
+}
+
+fn presses_until_rx(network: &Network) -> Option<u64> {
+    let broadcaster_targets = &network.broadcaster_targets;
+    let mut rules = network.modules.clone();
+    let rx_parent = rules
+        .iter()
+        .find_map(|(name, module)| module.outputs.contains(&&b"rx"[..]).then_some(*name))?;
+
+    broadcaster_targets
+        .iter()
+        .map(|&src| {
+            let mut queue = VecDeque::new();
+
+            for it in 1..=MAX_PRESSES {
+                queue.push_back((src, b"broadcaster".as_slice(), Pulse::Low));
+                let mut pulses = 0;
+                while let Some((name, parent_name, pulse)) = queue.pop_front() {
+                    pulses += 1;
+                    if pulses > MAX_PULSES {
+                        return None;
+                    }
+                    if name == rx_parent && pulse == Pulse::High {
+                        return Some(it);
+                    }
+                    perform_tick(&mut queue, &mut rules, name, parent_name, pulse);
+                }
+            }
+            None
+        })
+        .try_fold(1u64, |product, presses| product.checked_mul(presses?))
+}
+
+/// The [`Solution`] of this day.
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed<'a> = Network<'a>;
+    type Part1 = Option<u64>;
+    type Part2 = Option<u64>;
+
+    const DAY: u8 = 20;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-20/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(network: &Self::Parsed<'_>) -> Self::Part1 {
+        pulse_product(network)
+    }
+
+    fn part_2(network: &Self::Parsed<'_>) -> Self::Part2 {
+        presses_until_rx(network)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &[u8] = b"broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    const EXAMPLE_2: &[u8] = b"broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE_1), Ok(Some(32_000_000)));
+        assert_eq!(part_1(EXAMPLE_2), Ok(Some(11_687_500)));
+    }
+}
@@ -0,0 +1,235 @@
+//! [Day 21: Step Counter](https://adventofcode.com/2023/day/21)
+
+use bstr::ByteSlice;
+
+use crate::{
+    math,
+    parse::Parser,
+    search::{self, Reachable},
+    viz::{self, Frame, Sink, VizError},
+    Grid, ParseError, Solution,
+};
+
+/// Steps the elf has to walk in part 1.
+pub const PART_1_STEPS: usize = 64;
+/// Steps the elf has to walk in part 2.
+pub const PART_2_STEPS: u64 = 26_501_365;
+
+fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    let parser = Parser::new(Day21::DAY, input);
+    let grid = Grid::parse(&parser, input, b".#S")?;
+    if grid.width() != grid.height() {
+        return Err(parser.error(input, "a square map"));
+    }
+    let mut starts = input.find_iter(b"S");
+    match (starts.next(), starts.next()) {
+        (Some(_), None) => Ok(grid),
+        (None, _) => Err(parser.error(parser.end(), "a starting position `S`")),
+        (Some(_), Some(i)) => Err(parser.error(&input[i..=i], "a single starting position")),
+    }
+}
+
+/// Number of garden plots reachable in exactly `steps` steps.
+pub fn part_1(input: &[u8], steps: usize) -> Result<u32, ParseError> {
+    parse(input).map(|grid| reachable_plots(&grid, steps))
+}
+
+/// Number of garden plots reachable in exactly `steps` steps on the
+/// infinitely repeating map.
+///
+/// Only works for inputs laid out like the real ones: a square map with the
+/// start in the center and free rows and columns through it.
+pub fn part_2(input: &[u8], steps: u64) -> Result<u64, ParseError> {
+    parse(input).map(|grid| reachable_plots_repeating(&grid, steps))
+}
+
+/// Plots with a distance of at most `steps` and the same parity, the elf can
+/// waste any even number of steps by walking back and forth.
+fn reachable_at<S>(reachable: &Reachable<S>, steps: usize) -> impl Iterator<Item = &S> {
+    reachable
+        .distances
+        .iter()
+        .filter(move |&(_, &d)| d <= steps && d % 2 == steps % 2)
+        .map(|(state, _)| state)
+}
+
+fn count_reachable<S>(reachable: &Reachable<S>, steps: usize) -> usize {
+    reachable_at(reachable, steps).count()
+}
+
+fn reachable_plots(grid: &Grid<u8>, steps: usize) -> u32 {
+    count_reachable(&distances(grid, steps), steps) as u32
+}
+
+/// Distances from the start to the plots at most `steps` away.
+fn distances(grid: &Grid<u8>, steps: usize) -> Reachable<(usize, usize)> {
+    let start = grid.position(|&c| c == b'S').unwrap();
+    search::bfs_distances(
+        start,
+        |&(x, y)| {
+            grid.neighbors_4(x, y)
+                .filter(|&position| grid[position] != b'#')
+                .collect::<Vec<_>>()
+        },
+        steps,
+    )
+}
+
+fn reachable_plots_repeating(grid: &Grid<u8>, steps: u64) -> u64 {
+    let n = grid.width();
+    let (start_x, start_y) = grid.position(|&c| c == b'S').unwrap();
+    let targets = [n / 2, n / 2 + n, n / 2 + 2 * n];
+    let reachable = search::bfs_distances(
+        (start_x as isize, start_y as isize),
+        |&(x, y)| {
+            [(0, 1), (0, -1), (1, 0), (-1, 0)]
+                .into_iter()
+                .map(move |(dx, dy)| (x + dx, y + dy))
+                .filter(move |&(x, y)| *grid.get_wrapping(x, y) != b'#')
+        },
+        targets[2],
+    );
+    let samples = targets.map(|target| count_reachable(&reachable, target) as i64);
+
+    // The reachable plots grow quadratically with every repetition of the map.
+    math::extrapolate(&samples, (steps / n as u64) as i64) as u64
+}
+
+/// Draws the plots reachable in exactly 0 to [`PART_1_STEPS`] steps, see
+/// [`viz`].
+pub fn visualize(input: &[u8], sink: &mut dyn Sink) -> Result<(), VizError> {
+    let grid = parse(input)?;
+    let start = grid.position(|&c| c == b'S').unwrap();
+    let map = Frame::new(&grid, |&tile| {
+        if tile == b'#' {
+            viz::FEATURE
+        } else {
+            viz::BACKGROUND
+        }
+    });
+    let reachable = distances(&grid, PART_1_STEPS);
+    for steps in 0..=PART_1_STEPS {
+        let plots = reachable_at(&reachable, steps).copied();
+        sink.frame(
+            map.clone()
+                .paint(plots, viz::REACHED)
+                .paint([start], viz::MARK),
+        )?;
+    }
+    Ok(())
+}
+
+/// The [`Solution`] of this day.
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Parsed<'a> = Grid<u8>;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    const DAY: u8 = 21;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-21/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(grid: &Self::Parsed<'_>) -> Self::Part1 {
+        reachable_plots(grid, PART_1_STEPS)
+    }
+
+    fn part_2(grid: &Self::Parsed<'_>) -> Self::Part2 {
+        reachable_plots_repeating(grid, PART_2_STEPS)
+    }
+
+    fn warnings(grid: &Self::Parsed<'_>) -> Vec<String> {
+        let n = grid.width();
+        let (x, y) = grid.position(|&c| c == b'S').unwrap();
+        let laid_out = (x, y) == (n / 2, n / 2)
+            && PART_2_STEPS % n as u64 == (n / 2) as u64
+            && grid.row(y).iter().all(|&c| c != b'#')
+            && grid.column(x).all(|&c| c != b'#');
+        if laid_out {
+            return Vec::new();
+        }
+        vec![format!(
+            "part 2 needs the start in the center of the map with free paths through it, and \
+             {PART_2_STEPS} steps to reach an edge of a repetition of the map"
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fnv::FnvHashSet;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE, 6), Ok(16));
+    }
+
+    /// Moves the set of possible positions one step at a time.
+    fn reachable_plots_repeating_naive(grid: &Grid<u8>, steps: u64) -> u64 {
+        let (x, y) = grid.position(|&c| c == b'S').unwrap();
+        let mut positions = FnvHashSet::from_iter([(x as isize, y as isize)]);
+        for _ in 0..steps {
+            positions = positions
+                .iter()
+                .flat_map(|&(x, y)| [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)])
+                .filter(|&(x, y)| *grid.get_wrapping(x, y) != b'#')
+                .collect();
+        }
+        positions.len() as u64
+    }
+
+    /// Maps shaped like the real input: the start in the center of a square
+    /// of odd size, with a free border and free lines through the start.
+    fn garden() -> impl Strategy<Value = Grid<u8>> {
+        (2..6usize).prop_flat_map(|half| {
+            let n = 2 * half + 1;
+            prop::collection::vec(prop::bool::weighted(0.2), n * n).prop_map(move |rocks| {
+                let cells = rocks
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, rock)| {
+                        let (x, y) = (i % n, i / n);
+                        let free = [x, y].iter().any(|&c| c == 0 || c == n - 1 || c == half);
+                        match (x, y) {
+                            _ if (x, y) == (half, half) => b'S',
+                            _ if rock && !free => b'#',
+                            _ => b'.',
+                        }
+                    })
+                    .collect();
+                Grid::from_vec(n, cells)
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_reachable_plots_repeating(garden in garden(), repetitions in 2..5u64) {
+            let steps = garden.width() as u64 * repetitions + garden.width() as u64 / 2;
+            prop_assert_eq!(
+                reachable_plots_repeating(&garden, steps),
+                reachable_plots_repeating_naive(&garden, steps)
+            );
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! [Day 22: Sand Slabs](https://adventofcode.com/2023/day/22)
+
+use std::collections::VecDeque;
+
+use bstr::ByteSlice;
+use itertools::Itertools;
+use rstar::AABB;
+
+use crate::{parse::Parser, ParseError, Solution};
+
+#[derive(Debug, Clone)]
+struct Brick {
+    above: AABB<[i32; 3]>,
+    supported_by: u32,
+}
+
+#[derive(Debug, Clone)]
+struct TreeItem {
+    aabb: AABB<[i32; 3]>,
+    id: usize,
+}
+
+impl rstar::RTreeObject for TreeItem {
+    type Envelope = AABB<[i32; 3]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.aabb
+    }
+}
+
+/// The floor is narrow and the stack tall, so the bounds differ per axis.
+/// Small enough that the volume of any box around the bricks, even added to
+/// another one, fits in an `i32`.
+const MAX_SIDE: i32 = 1 << 5;
+const MAX_HEIGHT: i32 = 1 << 20;
+
+fn parse(input: &[u8]) -> Result<Vec<AABB<[i32; 3]>>, ParseError> {
+    let parser = Parser::new(Day22::DAY, input);
+    input
+        .lines()
+        .map(|brick_info| {
+            let (from, to) = parser.split_once(brick_info, "~")?;
+            let from: [i32; 3] = parser.number_array(from, ",")?;
+            let to = parser.number_array(to, ",")?;
+            if from[2].min(to[2]) < 1 {
+                return Err(parser.error(brick_info, "a brick above the ground at z = 0"));
+            }
+            if [from, to].iter().any(|&[x, y, z]| {
+                !(0..MAX_SIDE).contains(&x)
+                    || !(0..MAX_SIDE).contains(&y)
+                    || !(0..MAX_HEIGHT).contains(&z)
+            }) {
+                return Err(parser.error(
+                    brick_info,
+                    "x and y from 0 to 31 and z from 1 to 1048575",
+                ));
+            }
+
+            Ok(AABB::from_corners(from, to))
+        })
+        .collect()
+}
+
+/// Lets the bricks settle and returns the answers to both parts.
+pub fn solve(input: &[u8]) -> Result<(u32, u32), ParseError> {
+    parse(input).map(|bricks| settle(&bricks))
+}
+
+fn settle(bricks: &[AABB<[i32; 3]>]) -> (u32, u32) {
+    let line_cnt = bricks.len();
+    let mut rests_on = vec![vec![]; line_cnt];
+    let parsed = bricks
+        .iter()
+        .copied()
+        .sorted_by_key(|aabb: &AABB<[i32; 3]>| aabb.lower()[2])
+        .enumerate()
+        .map(|(id, aabb)| TreeItem { aabb, id })
+        .collect_vec();
+
+    let mut tree = rstar::RTree::new();
+
+    let bricks = parsed
+        .into_iter()
+        .map(|mut ti| {
+            let (supported_by, max_z_len, z_base) = if ti.aabb.lower()[2] == 1 {
+                (vec![], 0, 0)
+            } else {
+                let lower = ti.aabb.lower();
+                let upper = ti.aabb.upper();
+                let below_aabb =
+                    AABB::from_corners([lower[0], lower[1], 0], [upper[0], upper[1], lower[2] - 1]);
+
+                let candidates = tree.locate_in_envelope_intersecting(&below_aabb);
+                let (max_z_ids, max_z_len, max_z) = candidates.fold(
+                    (vec![], 0, 0),
+                    |(mut max_z_ids, mut max_z_len, mut max_z), r: &TreeItem| {
+                        let z = r.aabb.upper()[2];
+                        if z > max_z {
+                            max_z_ids.clear();
+                            max_z_ids.push(r.id);
+                            max_z = z;
+                            max_z_len = 1;
+                        } else if z == max_z {
+                            max_z_ids.push(r.id);
+                            max_z_len += 1;
+                        }
+                        (max_z_ids, max_z_len, max_z)
+                    },
+                );
+
+                (max_z_ids, max_z_len, max_z)
+            };
+
+            rests_on[ti.id] = supported_by;
+
+            let mut lower = ti.aabb.lower();
+            let mut upper = ti.aabb.upper();
+            upper[2] = upper[2] - lower[2] + z_base + 1;
+            lower[2] = z_base + 1;
+            ti.aabb = AABB::from_corners(lower, upper);
+
+            tree.insert(ti);
+
+            upper[2] += 1;
+            lower[2] = upper[2];
+
+            Brick {
+                above: AABB::from_corners(lower, upper),
+                supported_by: max_z_len,
+            }
+        })
+        .collect_vec();
+
+    let supports =
+        rests_on
+            .iter()
+            .enumerate()
+            .fold(vec![vec![]; line_cnt], |mut acc, (id, supported_by)| {
+                for &support in supported_by {
+                    acc[support].push(id);
+                }
+                acc
+            });
+
+    let mut removed = 0;
+    let mut supporting = vec![vec![]; line_cnt];
+    for i in 0..bricks.len() {
+        let above_ids = tree
+            .locate_in_envelope_intersecting(&bricks[i].above)
+            .map(|r| r.id)
+            .collect_vec();
+        supporting[i] = above_ids.clone();
+        if above_ids.iter().all(|&id| bricks[id].supported_by > 1) {
+            removed += 1_u32;
+        }
+    }
+
+    let mut total_removed = 0;
+    for bx in 0..bricks.len() {
+        let mut queue = VecDeque::from_iter(supporting[bx].iter().copied());
+        let mut visited = vec![0; bricks.len()];
+        visited[bx] = usize::MAX;
+
+        while let Some(bx) = queue.pop_front() {
+            visited[bx] += 1;
+            if visited[bx] == bricks[bx].supported_by as usize {
+                total_removed += 1;
+                for &by in &supports[bx] {
+                    queue.push_back(by);
+                }
+            }
+        }
+    }
+    (removed, total_removed)
+}
+
+/// Number of bricks that can be disintegrated without any other brick
+/// falling.
+pub fn part_1(input: &[u8]) -> Result<u32, ParseError> {
+    solve(input).map(|(removable, _)| removable)
+}
+
+/// Sum of the number of bricks that fall when each brick is disintegrated.
+pub fn part_2(input: &[u8]) -> Result<u32, ParseError> {
+    solve(input).map(|(_, falling)| falling)
+}
+
+/// The [`Solution`] of this day.
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed<'a> = Vec<AABB<[i32; 3]>>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const DAY: u8 = 22;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-22/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(bricks: &Self::Parsed<'_>) -> Self::Part1 {
+        settle(bricks).0
+    }
+
+    fn part_2(bricks: &Self::Parsed<'_>) -> Self::Part2 {
+        settle(bricks).1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(5));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(7));
+    }
+}
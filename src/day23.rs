@@ -0,0 +1,288 @@
+//! [Day 23: A Long Walk](https://adventofcode.com/2023/day/23)
+
+use fnv::FnvHashMap;
+use itertools::Itertools;
+
+use crate::{
+    parse::Parser,
+    search,
+    viz::{self, Frame, Sink, VizError},
+    Grid, ParseError, Solution,
+};
+
+/// Most junctions on the map, the search for the longest hike tries every
+/// path through them.
+const MAX_JUNCTIONS: usize = 36;
+
+fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    let parser = Parser::new(Day23::DAY, input);
+    let grid = Grid::parse(&parser, input, b"#.^v<>")?;
+    let width = grid.width();
+    if width != grid.height() || width < 3 {
+        return Err(parser.error(input, "a square map"));
+    }
+    // The hike starts in the second column of the first row and ends in the
+    // second to last column of the last row.
+    let first_line = &input[..width];
+    if first_line[1] != b'.' {
+        return Err(parser.error(&first_line[1..2], "the start of the trail"));
+    }
+    let last_line = &input[input.len() - width..];
+    if last_line[width - 2] != b'.' {
+        return Err(parser.error(&last_line[width - 2..width - 1], "the end of the trail"));
+    }
+    if junctions(&grid).count() > MAX_JUNCTIONS {
+        return Err(parser.error(
+            input,
+            format!("a map with at most {MAX_JUNCTIONS} junctions"),
+        ));
+    }
+    Ok(grid)
+}
+
+/// Open tiles where the trail branches.
+fn junctions(grid: &Grid<u8>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let open = |position: &(usize, usize)| grid[*position] != b'#';
+    grid.positions()
+        .filter(move |&(x, y)| open(&(x, y)) && grid.neighbors_4(x, y).filter(open).count() > 2)
+}
+
+/// Length of the longest hike, slopes can only be walked downhill, `None` if
+/// there is none.
+pub fn part_1(input: &[u8]) -> Result<Option<usize>, ParseError> {
+    parse(input).map(|grid| solve(&grid, false))
+}
+
+/// Length of the longest hike when slopes are treated as normal paths,
+/// `None` if there is none.
+pub fn part_2(input: &[u8]) -> Result<Option<usize>, ParseError> {
+    parse(input).map(|grid| solve(&grid, true))
+}
+
+fn solve(grid: &Grid<u8>, part_2: bool) -> Option<usize> {
+    let trails = Trails::new(grid, part_2);
+    let mut visited = vec![false; trails.junctions.len()];
+    longest_hike(&trails.edges, 0, 1, &mut visited, None)
+}
+
+/// The trail only branches at junctions, so the hike is a path through them.
+struct Trails<'a> {
+    grid: &'a Grid<u8>,
+    part_2: bool,
+    /// The start and end of the hike, followed by the junctions on the map.
+    junctions: Vec<(usize, usize)>,
+    index: FnvHashMap<(usize, usize), usize>,
+    /// The neighbours of each junction with the length of the trail to them.
+    edges: Vec<Vec<(usize, usize)>>,
+}
+
+impl<'a> Trails<'a> {
+    fn new(grid: &'a Grid<u8>, part_2: bool) -> Self {
+        let start = (1, 0);
+        let end = (grid.width() - 2, grid.height() - 1);
+        let junctions = [start, end]
+            .into_iter()
+            .chain(junctions(grid))
+            .collect_vec();
+        let index = junctions.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+        let mut trails = Self {
+            grid,
+            part_2,
+            junctions,
+            index,
+            edges: Vec::new(),
+        };
+
+        trails.edges = trails
+            .junctions
+            .iter()
+            .map(|&from| {
+                search::bfs_distances(from, |&position| trails.steps(from, position), usize::MAX)
+                    .distances
+                    .into_iter()
+                    .filter(|&(position, _)| position != from)
+                    .filter_map(|(position, length)| Some((*trails.index.get(&position)?, length)))
+                    .collect_vec()
+            })
+            .collect_vec();
+        trails
+    }
+
+    /// Tiles a hiker on a trail leaving the junction `from` can step to from
+    /// `position`, trails end at the next junction.
+    fn steps(&self, from: (usize, usize), position: (usize, usize)) -> Vec<(usize, usize)> {
+        if position != from && self.index.contains_key(&position) {
+            return Vec::new();
+        }
+        let offsets: &[(isize, isize)] = match self.grid[position] {
+            b'^' if !self.part_2 => &[(0, -1)],
+            b'v' if !self.part_2 => &[(0, 1)],
+            b'<' if !self.part_2 => &[(-1, 0)],
+            b'>' if !self.part_2 => &[(1, 0)],
+            _ => &[(0, -1), (1, 0), (0, 1), (-1, 0)],
+        };
+        offsets
+            .iter()
+            .filter_map(|&offset| self.grid.step(position, offset))
+            .filter(|&position| self.grid[position] != b'#')
+            .collect()
+    }
+
+    /// Tiles of the trail from junction `from` to its neighbour `to`.
+    fn trail(&self, from: usize, to: usize) -> Vec<(usize, usize)> {
+        let (from, to) = (self.junctions[from], self.junctions[to]);
+        let path = search::bfs(from, |&position| self.steps(from, position), |&p| p == to).path;
+        path.map_or_else(Vec::new, |path| path.states)
+    }
+}
+
+/// Length of the longest path from junction `from` to `to` that visits no
+/// junction twice, `edges` holds the neighbours of each junction with the
+/// length of the trail to them. If there is a `route`, it is set to the
+/// junctions of that path in order.
+fn longest_hike(
+    edges: &[Vec<(usize, usize)>],
+    from: usize,
+    to: usize,
+    visited: &mut [bool],
+    mut route: Option<&mut Vec<usize>>,
+) -> Option<usize> {
+    if from == to {
+        if let Some(route) = route {
+            *route = vec![to];
+        }
+        return Some(0);
+    }
+
+    visited[from] = true;
+    let mut longest = None;
+    // The route of the hike from the neighbour looked at.
+    let mut rest = route.is_some().then(Vec::new);
+    for &(next, length) in &edges[from] {
+        if visited[next] {
+            continue;
+        }
+        let Some(hike) = longest_hike(edges, next, to, visited, rest.as_mut()) else {
+            continue;
+        };
+        if longest.is_none_or(|longest| length + hike > longest) {
+            longest = Some(length + hike);
+            if let (Some(route), Some(rest)) = (route.as_deref_mut(), &rest) {
+                route.clear();
+                route.push(from);
+                route.extend(rest);
+            }
+        }
+    }
+    visited[from] = false;
+    longest
+}
+
+/// Draws the junctions of the trails, then the longest hikes of both parts
+/// one trail at a time, see [`viz`].
+pub fn visualize(input: &[u8], sink: &mut dyn Sink) -> Result<(), VizError> {
+    let grid = parse(input)?;
+    let mut map = Frame::new(&grid, |&tile| match tile {
+        b'#' => viz::FEATURE,
+        b'.' => viz::BACKGROUND,
+        // Slopes.
+        _ => [60, 60, 90],
+    });
+    map.paint(Trails::new(&grid, false).junctions, viz::MARK);
+    sink.frame(&map)?;
+
+    for part_2 in [false, true] {
+        let trails = Trails::new(&grid, part_2);
+        let mut visited = vec![false; trails.junctions.len()];
+        let mut route = Vec::new();
+        longest_hike(&trails.edges, 0, 1, &mut visited, Some(&mut route));
+        let mut frame = map.clone();
+        for (from, to) in route.into_iter().tuple_windows() {
+            sink.frame(frame.paint(trails.trail(from, to), viz::PATH))?;
+        }
+    }
+    Ok(())
+}
+
+/// The [`Solution`] of this day.
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Parsed<'a> = Grid<u8>;
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
+
+    const DAY: u8 = 23;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-23/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(grid: &Self::Parsed<'_>) -> Self::Part1 {
+        solve(grid, false)
+    }
+
+    fn part_2(grid: &Self::Parsed<'_>) -> Self::Part2 {
+        solve(grid, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(Some(94)));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(Some(154)));
+    }
+
+    #[test]
+    fn test_route() {
+        let grid = parse(EXAMPLE).unwrap();
+        for (part_2, longest) in [(false, 94), (true, 154)] {
+            let trails = Trails::new(&grid, part_2);
+            let mut visited = vec![false; trails.junctions.len()];
+            let mut route = Vec::new();
+            let hike = longest_hike(&trails.edges, 0, 1, &mut visited, Some(&mut route));
+            assert_eq!(hike, Some(longest));
+            assert_eq!((route.first(), route.last()), (Some(&0), Some(&1)));
+            let length = route
+                .iter()
+                .tuple_windows()
+                .map(|(&from, &to)| trails.edges[from].iter().find(|e| e.0 == to).unwrap().1)
+                .sum::<usize>();
+            assert_eq!(length, longest);
+        }
+    }
+}
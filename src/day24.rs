@@ -0,0 +1,226 @@
+//! [Day 24: Never Tell Me The Odds](https://adventofcode.com/2023/day/24)
+
+use bstr::ByteSlice;
+use itertools::Itertools;
+
+use crate::{parse::Parser, ParseError, Solution};
+
+/// Lower bound of the test area of part 1.
+pub const TEST_AREA_START: f64 = 200_000_000_000_000.0;
+/// Upper bound of the test area of part 1.
+pub const TEST_AREA_STOP: f64 = 400_000_000_000_000.0;
+
+/// Position and velocity of a hailstone.
+type Hailstone = ([i64; 3], [i64; 3]);
+
+fn parse_vector(parser: &Parser, s: &[u8]) -> Result<[i64; 3], ParseError> {
+    s.split_str(",")
+        .map(|n| parser.number(n.trim()))
+        .collect::<Result<Vec<_>, _>>()?
+        .try_into()
+        .map_err(|_| parser.error(s, "3 numbers separated by `,`"))
+}
+
+fn parse(input: &[u8]) -> Result<Vec<Hailstone>, ParseError> {
+    let parser = Parser::new(Day24::DAY, input);
+    input
+        .lines()
+        .map(|line| {
+            let (pos, vel) = parser.split_once(line, " @ ")?;
+            Ok((parse_vector(&parser, pos)?, parse_vector(&parser, vel)?))
+        })
+        .collect()
+}
+
+/// Number of hailstone pairs whose future paths cross within the test area,
+/// i.e. with x and y between `start` and `stop`.
+pub fn part_1(input: &[u8], start: f64, stop: f64) -> Result<usize, ParseError> {
+    parse(input).map(|hailstones| crossing_paths(&hailstones, start, stop))
+}
+
+/// Sum of the coordinates of the position to throw the rock from so that
+/// it hits every hailstone, `None` if there is no such position or the rock
+/// moves faster than 1000 along x or y.
+pub fn part_2(input: &[u8]) -> Result<Option<i64>, ParseError> {
+    parse(input).map(|hailstones| rock_position(&hailstones))
+}
+
+fn crossing_paths(hailstones: &[Hailstone], start: f64, stop: f64) -> usize {
+    hailstones
+        .iter()
+        .map(|&([px, py, _], [vx, vy, _])| ((px as f64, py as f64), (vx as f64, vy as f64)))
+        .tuple_combinations()
+        .filter(|&(a, b)| {
+            let ((x1, y1), (vx1, vy1)) = a;
+            let ((x2, y2), (vx2, vy2)) = b;
+
+            let d = vx1.mul_add(vy2, -(vy1 * vx2));
+
+            if d.abs() <= f64::EPSILON {
+                return false;
+            }
+
+            let t = (x2 - x1).mul_add(vy2, -((y2 - y1) * vx2)) / d;
+            let u = (x2 - x1).mul_add(vy1, -((y2 - y1) * vx1)) / d;
+
+            if t < 0.0 || u < 0.0 {
+                return false;
+            }
+
+            let x = t.mul_add(vx1, x1);
+            let y = t.mul_add(vy1, y1);
+
+            (start..=stop).contains(&x) && (start..=stop).contains(&y)
+        })
+        .count()
+}
+
+/// Fastest the rock may move along x and y, which bounds the search for its
+/// velocity. The rocks of the puzzle move a few hundred at most.
+const MAX_SPEED: i64 = 1000;
+
+/// Searches the velocity of the rock in x and y, slowest first. Seen from a
+/// rock moving at the right velocity, every hailstone passes through its
+/// starting position, so two of them fix it and the rest confirm it.
+fn rock_position(hailstones: &[Hailstone]) -> Option<i64> {
+    (0..=MAX_SPEED)
+        .flat_map(|r| (-r..=r).flat_map(move |i| [(i, -r), (i, r), (-r, i), (r, i)]))
+        .find_map(|(vx, vy)| throw(hailstones, vx, vy))
+}
+
+/// A hailstone seen from a rock moving at `vx, vy` in x and y, with its
+/// position and its relative velocity in x and y.
+fn relative(&(p, v): &Hailstone, vx: i64, vy: i64) -> ([i128; 3], [i128; 2]) {
+    (
+        p.map(i128::from),
+        [
+            i128::from(v[0]) - i128::from(vx),
+            i128::from(v[1]) - i128::from(vy),
+        ],
+    )
+}
+
+/// When a hailstone at `p` moving at `v` relative to the rock passes `x, y`:
+/// `Some(None)` if it stands still there, `None` if it never does. Products
+/// that don't fit in an `i128` count as never.
+fn time_at(x: i128, y: i128, p: [i128; 3], v: [i128; 2]) -> Option<Option<i128>> {
+    let (dx, dy) = (x.checked_sub(p[0])?, y.checked_sub(p[1])?);
+    if dx.checked_mul(v[1])? != dy.checked_mul(v[0])? {
+        return None;
+    }
+    let (d, v) = match v {
+        [0, 0] => return (dx == 0 && dy == 0).then_some(None),
+        [0, v] => (dy, v),
+        [v, _] => (dx, v),
+    };
+    (d % v == 0 && d / v >= 0).then_some(Some(d / v))
+}
+
+/// Sum of the coordinates of the position a rock moving at `vx, vy` in x and
+/// y has to start from to hit every hailstone, if there is one.
+fn throw(hailstones: &[Hailstone], vx: i64, vy: i64) -> Option<i64> {
+    let (p0, a) = relative(hailstones.first()?, vx, vy);
+    // The start in x and y, from the first hailstone and one that doesn't
+    // move parallel to it.
+    let (x, y) = hailstones[1..].iter().find_map(|stone| {
+        let (p, b) = relative(stone, vx, vy);
+        let d = a[0].checked_mul(b[1])?.checked_sub(a[1].checked_mul(b[0])?)?;
+        if d == 0 {
+            return None;
+        }
+        let cross = (p[0] - p0[0])
+            .checked_mul(b[1])?
+            .checked_sub((p[1] - p0[1]).checked_mul(b[0])?)?;
+        if cross % d != 0 || cross / d < 0 {
+            return Some(None);
+        }
+        let t = cross / d;
+        Some(Some((
+            p0[0].checked_add(a[0].checked_mul(t)?)?,
+            p0[1].checked_add(a[1].checked_mul(t)?)?,
+        )))
+    })??;
+
+    // Two hailstones hit at different times fix the throw in z.
+    let mut hits = Vec::with_capacity(2);
+    for stone in hailstones {
+        let (p, v) = relative(stone, vx, vy);
+        let t = time_at(x, y, p, v)?;
+        if let Some(t) = t {
+            if hits.len() < 2 && hits.first().is_none_or(|&(t0, _, _)| t0 != t) {
+                hits.push((t, p[2], i128::from(stone.1[2])));
+            }
+        }
+    }
+    let &[(t0, z0, vz0), (t1, z1, vz1)] = hits.as_slice() else {
+        return None;
+    };
+    let moved = (z0 - z1)
+        .checked_add(vz0.checked_mul(t0)?)?
+        .checked_sub(vz1.checked_mul(t1)?)?;
+    if moved % (t0 - t1) != 0 {
+        return None;
+    }
+    let vz = moved / (t0 - t1);
+    let z = z0.checked_add((vz0 - vz).checked_mul(t0)?)?;
+
+    for stone in hailstones {
+        let (p, v) = relative(stone, vx, vy);
+        let (gap, closing) = (z.checked_sub(p[2])?, i128::from(stone.1[2]).checked_sub(vz)?);
+        let hit = match time_at(x, y, p, v)? {
+            Some(t) => closing.checked_mul(t)? == gap,
+            None if closing == 0 => gap == 0,
+            None => gap % closing == 0 && gap / closing >= 0,
+        };
+        if !hit {
+            return None;
+        }
+    }
+    i64::try_from(x.checked_add(y)?.checked_add(z)?).ok()
+}
+
+/// The [`Solution`] of this day.
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Parsed<'a> = Vec<Hailstone>;
+    type Part1 = usize;
+    type Part2 = Option<i64>;
+
+    const DAY: u8 = 24;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-24/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(hailstones: &Self::Parsed<'_>) -> Self::Part1 {
+        crossing_paths(hailstones, TEST_AREA_START, TEST_AREA_STOP)
+    }
+
+    fn part_2(hailstones: &Self::Parsed<'_>) -> Self::Part2 {
+        rock_position(hailstones)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE, 7.0, 27.0), Ok(2));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE), Ok(Some(47)));
+    }
+}
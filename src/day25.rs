@@ -0,0 +1,193 @@
+//! [Day 25: Snowverload](https://adventofcode.com/2023/day/25)
+
+use std::collections::VecDeque;
+
+use bstr::ByteSlice;
+use fnv::FnvHashMap;
+use petgraph::{dot, prelude::*};
+
+use crate::{parse::Parser, NoAnswer, ParseError, Solution};
+
+/// The wires between the components.
+fn parse(input: &[u8]) -> Result<Vec<(&str, &str)>, ParseError> {
+    let parser = Parser::new(Day25::DAY, input);
+
+    let mut wires = Vec::new();
+    for line in input.lines() {
+        let (from, to) = parser.split_once(line, ": ")?;
+        let from = component(&parser, from)?;
+        for to in to.split_str(" ") {
+            wires.push((from, component(&parser, to)?));
+        }
+    }
+    Ok(wires)
+}
+
+fn component<'a>(parser: &Parser, name: &'a [u8]) -> Result<&'a str, ParseError> {
+    match std::str::from_utf8(name) {
+        Ok(name) if !name.is_empty() => Ok(name),
+        _ => Err(parser.error(name, "the name of a component")),
+    }
+}
+
+/// Product of the sizes of the two groups left after cutting three wires,
+/// `None` if no three wires split the components in two.
+pub fn part_1(input: &[u8]) -> Result<Option<usize>, ParseError> {
+    parse(input).map(|wires| group_sizes(&wires))
+}
+
+/// The wiring as a Graphviz graph, where the three wires to cut are easy to
+/// spot.
+pub fn wiring_dot(input: &[u8]) -> Result<String, ParseError> {
+    let wires = parse(input)?;
+    let graph = UnGraphMap::<_, ()>::from_edges(wires);
+    Ok(format!(
+        "{:?}",
+        dot::Dot::with_config(&graph, &[dot::Config::EdgeNoLabel])
+    ))
+}
+
+fn group_sizes(wires: &[(&str, &str)]) -> Option<usize> {
+    let mut graph = UnGraphMap::new();
+
+    for &(from, to) in wires {
+        graph.add_edge(from, to, ());
+    }
+
+    let index = graph
+        .nodes()
+        .enumerate()
+        .map(|(i, name)| (name, i))
+        .collect::<FnvHashMap<_, _>>();
+    let edges = graph
+        .all_edges()
+        .map(|(from, to, _)| (index[from], index[to]))
+        .collect::<Vec<_>>();
+    min_cut_side(index.len(), &edges).map(|side| side * (index.len() - side))
+}
+
+/// Size of one side after cutting exactly three wires, `None` if no three
+/// wires split the graph in two groups of more than one component.
+///
+/// The flow starts at a component with the most wires, which three cut
+/// wires don't cut off on its own unless they are all it has. Some other
+/// component lies on the other side of the cut, so there are exactly three
+/// wire-disjoint paths to it. Whatever is still reachable after sending one
+/// unit of flow along each of them is the side of the source. A component
+/// with only three wires is cut off by those, that side is skipped.
+fn min_cut_side(nodes: usize, edges: &[(usize, usize)]) -> Option<usize> {
+    let mut adjacent = vec![Vec::new(); nodes];
+    for (i, &(a, b)) in edges.iter().enumerate() {
+        adjacent[a].push((b, i));
+        adjacent[b].push((a, i));
+    }
+    let source = (0..nodes).max_by_key(|&node| adjacent[node].len())?;
+
+    (0..nodes).filter(|&sink| sink != source).find_map(|sink| {
+        // Flow along each wire, positive from its first to its second end.
+        let mut flow = vec![0i8; edges.len()];
+        for paths in 0..=3 {
+            let mut parent = vec![None; nodes];
+            let mut seen = vec![false; nodes];
+            let mut queue = VecDeque::from([source]);
+            seen[source] = true;
+            while let Some(node) = queue.pop_front() {
+                for &(next, wire) in &adjacent[node] {
+                    let forward = edges[wire].0 == node;
+                    let residual = if forward {
+                        1 - flow[wire]
+                    } else {
+                        1 + flow[wire]
+                    };
+                    if residual > 0 && !seen[next] {
+                        seen[next] = true;
+                        parent[next] = Some((node, wire));
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            if !seen[sink] {
+                let side = seen.iter().filter(|&&s| s).count();
+                return (paths == 3 && side != 1 && side != nodes - 1).then_some(side);
+            }
+            let mut node = sink;
+            while let Some((prev, wire)) = parent[node] {
+                flow[wire] += if edges[wire].0 == prev { 1 } else { -1 };
+                node = prev;
+            }
+        }
+        None
+    })
+}
+
+/// The [`Solution`] of this day.
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Parsed<'a> = Vec<(&'a str, &'a str)>;
+    type Part1 = Option<usize>;
+    type Part2 = NoAnswer;
+
+    const DAY: u8 = 25;
+    #[cfg(feature = "embed-input")]
+    const INPUT: Option<&'static [u8]> = Some(include_bytes!("day-25/input.txt"));
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(wires: &Self::Parsed<'_>) -> Self::Part1 {
+        group_sizes(wires)
+    }
+
+    fn part_2(_wires: &Self::Parsed<'_>) -> Self::Part2 {
+        NoAnswer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE), Ok(Some(54)));
+        assert_eq!(part_1(b"a: b c d e\nb: c d e\nc: d e\nd: e"), Ok(None));
+    }
+
+    #[test]
+    fn test_degree_3() {
+        // `a` comes first and has only the three wires to its own group.
+        let input = b"a: b c d
+b: c d e
+c: d f
+d: g
+e: f g h i
+f: g h i
+g: h i
+h: i";
+        assert_eq!(part_1(input), Ok(Some(4 * 5)));
+    }
+
+    #[test]
+    fn test_wiring_dot() {
+        let dot = wiring_dot(b"a: b c\nb: c").unwrap();
+        assert!(dot.starts_with("graph {"), "{dot}");
+        assert_eq!(dot.matches(" -- ").count(), 3);
+    }
+}
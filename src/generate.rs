@@ -0,0 +1,1362 @@
+//! Random puzzle inputs of any size, for stress tests and scaling benchmarks.
+//!
+//! Each day has a [`Generator`] that turns a size and a seed into an input.
+//! The same size and seed always give the same input. What the size counts
+//! depends on the day, see [`Generator::size`]; it defaults to the size of the
+//! real inputs.
+//!
+//! Besides having the right format, the inputs keep the properties the
+//! solutions rely on that the puzzles only promise implicitly, for example:
+//!
+//! - the maps of the almanac of day 5 are permutations of `0..2³²`,
+//! - the ghosts of day 8 return to their `..Z` node at a fixed period,
+//! - every pattern of day 13 has exactly one line of reflection and exactly one
+//!   with a smudge,
+//! - the module network of day 20 is made of binary counters feeding `rx`, each
+//!   resetting after a distinct prime number of presses,
+//! - the garden of day 21 has a free border and free centre lines, and a side
+//!   for which the steps of part 2 end on the edge of a repetition,
+//! - one rock thrown from an integer position hits all hailstones of day 24,
+//! - the components of day 25 split into two groups that are connected by
+//!   exactly three wires.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet, VecDeque},
+    fmt,
+    ops::{Range, RangeInclusive},
+};
+
+use itertools::{izip, Itertools};
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng, SeedableRng,
+};
+use rand_chacha::ChaCha8Rng;
+
+use crate::Grid;
+
+const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Generates the inputs of one day.
+pub struct Generator {
+    pub day: u8,
+    /// What the size of an input counts, e.g. `hands`.
+    pub size: &'static str,
+    /// The size of the real inputs.
+    pub default_size: usize,
+    /// The sizes the generator supports.
+    pub sizes: &'static [RangeInclusive<usize>],
+    generate: fn(&mut ChaCha8Rng, usize) -> String,
+}
+
+impl Generator {
+    /// A random input of `size`, without a trailing newline.
+    pub fn generate(&self, size: usize, seed: u64) -> Result<String, SizeError> {
+        if !self.sizes.iter().any(|sizes| sizes.contains(&size)) {
+            return Err(SizeError {
+                day: self.day,
+                size,
+                what: self.size,
+                sizes: self.sizes,
+            });
+        }
+        Ok((self.generate)(&mut ChaCha8Rng::seed_from_u64(seed), size))
+    }
+}
+
+/// The size passed to [`Generator::generate`] is not supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeError {
+    pub day: u8,
+    pub size: usize,
+    what: &'static str,
+    sizes: &'static [RangeInclusive<usize>],
+}
+
+impl fmt::Display for SizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sizes = self
+            .sizes
+            .iter()
+            .map(|sizes| match sizes.start() == sizes.end() {
+                true => sizes.start().to_string(),
+                false => format!("{} to {}", sizes.start(), sizes.end()),
+            })
+            .collect_vec();
+        let sizes = match sizes.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} or {last}", rest.join(", ")),
+            _ => sizes.concat(),
+        };
+        write!(
+            f,
+            "day {} takes {sizes} {}, not {}",
+            self.day, self.what, self.size
+        )
+    }
+}
+
+/// The generator of every day, `GENERATORS[0]` is day 1.
+pub static GENERATORS: [Generator; 25] = [
+    Generator {
+        day: 1,
+        size: "lines",
+        default_size: 1000,
+        sizes: &[1..=1_000_000],
+        generate: day01,
+    },
+    Generator {
+        day: 2,
+        size: "games",
+        default_size: 100,
+        sizes: &[1..=1_000_000],
+        generate: day02,
+    },
+    Generator {
+        day: 3,
+        size: "as the side of the schematic",
+        default_size: 140,
+        sizes: &[1..=10_000],
+        generate: day03,
+    },
+    Generator {
+        day: 4,
+        size: "cards",
+        default_size: 200,
+        sizes: &[1..=1_000_000],
+        generate: day04,
+    },
+    Generator {
+        day: 5,
+        size: "rules per map",
+        default_size: 30,
+        sizes: &[1..=100_000],
+        generate: day05,
+    },
+    Generator {
+        day: 6,
+        size: "races",
+        default_size: 4,
+        sizes: &[1..=4],
+        generate: day06,
+    },
+    Generator {
+        day: 7,
+        size: "hands",
+        default_size: 1000,
+        sizes: &[1..=100_000],
+        generate: day07,
+    },
+    Generator {
+        day: 8,
+        size: "ghosts",
+        default_size: 6,
+        sizes: &[1..=8],
+        generate: day08,
+    },
+    Generator {
+        day: 9,
+        size: "histories",
+        default_size: 200,
+        sizes: &[1..=1_000_000],
+        generate: day09,
+    },
+    Generator {
+        day: 10,
+        size: "as the side of the map",
+        default_size: 140,
+        sizes: &[4..=5000],
+        generate: day10,
+    },
+    Generator {
+        day: 11,
+        size: "as the side of the image",
+        default_size: 140,
+        sizes: &[1..=1000],
+        generate: day11,
+    },
+    Generator {
+        day: 12,
+        size: "rows",
+        default_size: 1000,
+        sizes: &[1..=100_000],
+        generate: day12,
+    },
+    Generator {
+        day: 13,
+        size: "patterns",
+        default_size: 100,
+        sizes: &[1..=100_000],
+        generate: day13,
+    },
+    Generator {
+        day: 14,
+        size: "as the side of the platform",
+        default_size: 100,
+        sizes: &[1..=1000],
+        generate: day14,
+    },
+    Generator {
+        day: 15,
+        size: "steps",
+        default_size: 4000,
+        sizes: &[1..=1_000_000],
+        generate: day15,
+    },
+    Generator {
+        day: 16,
+        size: "as the side of the contraption",
+        default_size: 110,
+        sizes: &[1..=1000],
+        generate: day16,
+    },
+    Generator {
+        day: 17,
+        size: "as the side of the map",
+        default_size: 141,
+        sizes: &[5..=1000],
+        generate: day17,
+    },
+    Generator {
+        day: 18,
+        size: "as the side of the grid the trench is traced on",
+        default_size: 28,
+        sizes: &[1..=500],
+        generate: day18,
+    },
+    Generator {
+        day: 19,
+        size: "workflows",
+        default_size: 550,
+        sizes: &[1..=10_000],
+        generate: day19,
+    },
+    Generator {
+        day: 20,
+        size: "counters",
+        default_size: 4,
+        sizes: &[1..=5],
+        generate: day20,
+    },
+    Generator {
+        day: 21,
+        size: "as the side of the map",
+        default_size: 131,
+        sizes: &[3..=3, 131..=131, 393..=393],
+        generate: day21,
+    },
+    Generator {
+        day: 22,
+        size: "bricks",
+        default_size: 1300,
+        sizes: &[1..=100_000],
+        generate: day22,
+    },
+    Generator {
+        day: 23,
+        size: "junctions per side",
+        default_size: 6,
+        sizes: &[1..=6],
+        generate: day23,
+    },
+    Generator {
+        day: 24,
+        size: "hailstones",
+        default_size: 300,
+        sizes: &[3..=10_000],
+        generate: day24,
+    },
+    Generator {
+        day: 25,
+        size: "components",
+        default_size: 1500,
+        sizes: &[10..=10_000],
+        generate: day25,
+    },
+];
+
+pub fn generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.get(usize::from(day).checked_sub(1)?)
+}
+
+/// `len` random bytes of `alphabet`.
+fn letters(rng: &mut ChaCha8Rng, len: usize, alphabet: &[u8]) -> String {
+    (0..len)
+        .map(|_| char::from(*alphabet.choose(rng).unwrap()))
+        .collect()
+}
+
+/// A name made by `make` that is not in `taken` yet, and adds it there.
+fn fresh_name(
+    rng: &mut ChaCha8Rng,
+    taken: &mut HashSet<String>,
+    mut make: impl FnMut(&mut ChaCha8Rng) -> String,
+) -> String {
+    loop {
+        let name = make(rng);
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// `count` distinct numbers of `range` in ascending order.
+fn distinct_sorted(rng: &mut ChaCha8Rng, count: usize, range: Range<u64>) -> Vec<u64> {
+    let mut numbers = BTreeSet::new();
+    while numbers.len() < count {
+        numbers.insert(rng.gen_range(range.clone()));
+    }
+    numbers.into_iter().collect()
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+fn render(grid: &Grid<u8>) -> String {
+    grid.rows().map(String::from_utf8_lossy).join("\n")
+}
+
+const NORTH: u8 = 1;
+const EAST: u8 = 2;
+const SOUTH: u8 = 4;
+const WEST: u8 = 8;
+
+fn opposite(direction: u8) -> u8 {
+    (direction << 2 | direction >> 2) & 0xf
+}
+
+fn offset(direction: u8) -> (isize, isize) {
+    match direction {
+        NORTH => (0, -1),
+        EAST => (1, 0),
+        SOUTH => (0, 1),
+        _ => (-1, 0),
+    }
+}
+
+/// A random tree on a grid of `nodes` by `nodes`, grown from a random node
+/// until it covers `fill` of them. Its nodes are drawn as squares of `block`
+/// by `block` cells, the edges between them as corridors of the same width.
+///
+/// The gaps between the blocks keep the region free of holes and of cells
+/// that only touch at a corner, so its outline is a single simple loop.
+fn tree_region(rng: &mut ChaCha8Rng, nodes: usize, block: usize, fill: f64) -> Grid<bool> {
+    let pitch = block + 1;
+    let mut region = Grid::new(nodes * pitch - 1, nodes * pitch - 1, false);
+    let mut in_tree = Grid::new(nodes, nodes, false);
+    let target = ((nodes * nodes) as f64 * fill).ceil() as usize;
+
+    let start = (rng.gen_range(0..nodes), rng.gen_range(0..nodes));
+    let mut frontier = vec![(start, start)];
+    let mut count = 0;
+    while count < target && !frontier.is_empty() {
+        let (from, to) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        if in_tree[to] {
+            continue;
+        }
+        in_tree[to] = true;
+        count += 1;
+        // The block of `to` together with the corridor from `from`.
+        for y in from.1.min(to.1) * pitch..from.1.max(to.1) * pitch + block {
+            for x in from.0.min(to.0) * pitch..from.0.max(to.0) * pitch + block {
+                region[(x, y)] = true;
+            }
+        }
+        frontier.extend(
+            in_tree
+                .neighbors_4(to.0, to.1)
+                .filter(|&next| !in_tree[next])
+                .map(|next| (to, next)),
+        );
+    }
+    region
+}
+
+/// The outline of `region` on the corners of its cells: the directions the
+/// outline leaves each corner in, as bits of `NORTH`, `EAST`, `SOUTH` and
+/// `WEST`.
+fn outline(region: &Grid<bool>) -> Grid<u8> {
+    let (width, height) = (region.width(), region.height());
+    let inside = |x: usize, dx: isize, y: usize, dy: isize| {
+        let (Some(x), Some(y)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+            return false;
+        };
+        region.get(x, y).copied().unwrap_or(false)
+    };
+
+    let mut corners = Grid::new(width + 1, height + 1, 0);
+    for y in 0..=height {
+        for x in 0..=width {
+            // The edge east of the corner (x, y) runs between the cells
+            // (x, y - 1) and (x, y), the edge south of it between the cells
+            // (x - 1, y) and (x, y).
+            if x < width && inside(x, 0, y, -1) != inside(x, 0, y, 0) {
+                corners[(x, y)] |= EAST;
+                corners[(x + 1, y)] |= WEST;
+            }
+            if y < height && inside(x, -1, y, 0) != inside(x, 0, y, 0) {
+                corners[(x, y)] |= SOUTH;
+                corners[(x, y + 1)] |= NORTH;
+            }
+        }
+    }
+    corners
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Letters with digits and spelled out digits mixed in, at least one digit
+/// per line.
+fn day01(rng: &mut ChaCha8Rng, lines: usize) -> String {
+    (0..lines)
+        .map(|_| {
+            let tokens = rng.gen_range(2..=8);
+            let digit_at = rng.gen_range(0..tokens);
+            let mut line = String::new();
+            for i in 0..tokens {
+                if i == digit_at || rng.gen_bool(0.2) {
+                    line.push(char::from(rng.gen_range(b'1'..=b'9')));
+                } else if rng.gen_bool(0.3) {
+                    line.push_str(DIGIT_WORDS.choose(rng).unwrap());
+                } else {
+                    let len = rng.gen_range(1..=5);
+                    line.push_str(&letters(rng, len, LOWER));
+                }
+            }
+            line
+        })
+        .join("\n")
+}
+
+fn day02(rng: &mut ChaCha8Rng, games: usize) -> String {
+    (1..=games)
+        .map(|id| {
+            let handfuls = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    let mut colours = ["red", "green", "blue"];
+                    colours.shuffle(rng);
+                    let shown = rng.gen_range(1..=3);
+                    colours[..shown]
+                        .iter()
+                        .map(|colour| format!("{} {colour}", rng.gen_range(1..=20)))
+                        .join(", ")
+                })
+                .join("; ");
+            format!("Game {id}: {handfuls}")
+        })
+        .join("\n")
+}
+
+fn day03(rng: &mut ChaCha8Rng, side: usize) -> String {
+    let mut schematic = Grid::new(side, side, b'.');
+    for y in 0..side {
+        let mut x = rng.gen_range(0..4);
+        loop {
+            let digits = rng.gen_range(1..=3u32);
+            if x + digits as usize > side {
+                break;
+            }
+            let number = rng.gen_range(10u32.pow(digits - 1)..10u32.pow(digits));
+            for (i, digit) in number.to_string().bytes().enumerate() {
+                schematic[(x + i, y)] = digit;
+            }
+            x += digits as usize + rng.gen_range(1..=8);
+        }
+    }
+    for _ in 0..side * side / 16 {
+        let position = (rng.gen_range(0..side), rng.gen_range(0..side));
+        if schematic[position] == b'.' {
+            schematic[position] = match rng.gen_bool(0.4) {
+                true => b'*',
+                false => *b"#+$/@%=&-".choose(rng).unwrap(),
+            };
+        }
+    }
+    render(&schematic)
+}
+
+/// Cards with 10 winning numbers and 25 numbers each.
+fn day04(rng: &mut ChaCha8Rng, cards: usize) -> String {
+    let width = cards.to_string().len();
+    let numbers = |numbers: &[u32]| numbers.iter().map(|n| format!("{n:>2}")).join(" ");
+    let mut pool = (1..100).collect_vec();
+    (0..cards)
+        .map(|i| {
+            // Cards only win copies of cards up to the end of the table. The
+            // last 10 of every 40 cards win nothing, which keeps the number of
+            // copies from growing exponentially over the whole table.
+            let matches = match i % 40 >= 30 || rng.gen_bool(0.4) {
+                true => 0,
+                false => rng.gen_range(1..=10).min(cards - i - 1),
+            };
+            let (chosen, _) = pool.partial_shuffle(rng, 35);
+            let (winning, others) = chosen.split_at(10);
+            let mut mine = [&winning[..matches], &others[..25 - matches]].concat();
+            mine.shuffle(rng);
+            format!(
+                "Card {:>width$}: {} | {}",
+                i + 1,
+                numbers(winning),
+                numbers(&mine)
+            )
+        })
+        .join("\n")
+}
+
+/// The maps of the almanac permute the numbers below this.
+const ALMANAC_RANGE: u64 = 1 << 32;
+
+fn day05(rng: &mut ChaCha8Rng, rules: usize) -> String {
+    // Disjoint seed ranges covering at most half of the numbers.
+    let mut seeds = distinct_sorted(rng, 20, 0..ALMANAC_RANGE)
+        .chunks_exact(2)
+        .map(|range| {
+            let len = rng.gen_range(1..=(range[1] - range[0]).div_ceil(2));
+            (range[0], len)
+        })
+        .collect_vec();
+    seeds.shuffle(rng);
+    let mut almanac = format!(
+        "seeds: {}",
+        seeds
+            .iter()
+            .map(|(start, len)| format!("{start} {len}"))
+            .join(" ")
+    );
+
+    for name in [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ] {
+        // Cut the numbers into `rules` ranges and lay them out again in a
+        // random order, rules that map a range onto itself are left out.
+        let mut bounds = distinct_sorted(rng, rules - 1, 1..ALMANAC_RANGE);
+        bounds.insert(0, 0);
+        bounds.push(ALMANAC_RANGE);
+        let mut ranges = bounds
+            .windows(2)
+            .map(|range| (range[0], range[1] - range[0]))
+            .collect_vec();
+        ranges.shuffle(rng);
+        let mut map = Vec::new();
+        let mut dst = 0;
+        for (src, len) in ranges {
+            if dst != src {
+                map.push((dst, src, len));
+            }
+            dst += len;
+        }
+        map.shuffle(rng);
+
+        almanac.push_str(&format!("\n\n{name} map:"));
+        for (dst, src, len) in map {
+            almanac.push_str(&format!("\n{dst} {src} {len}"));
+        }
+    }
+    almanac
+}
+
+/// Races with two digit times and records that can be beaten, also when read
+/// as a single race.
+fn day06(rng: &mut ChaCha8Rng, races: usize) -> String {
+    let best = |time: u64| u128::from(time / 2) * u128::from(time - time / 2);
+    loop {
+        let times = (0..races).map(|_| rng.gen_range(10..100)).collect_vec();
+        let distances = times
+            .iter()
+            .map(|&time| {
+                let best = best(time) as u64;
+                rng.gen_range(best / 2..best)
+            })
+            .collect_vec();
+
+        let kerned = |numbers: &[u64]| numbers.iter().join("").parse::<u64>().ok();
+        let (Some(time), Some(distance)) = (kerned(&times), kerned(&distances)) else {
+            continue;
+        };
+        if best(time) <= u128::from(distance) {
+            continue;
+        }
+
+        let width = distances.iter().max().unwrap().to_string().len() + 3;
+        let line = |label: &str, numbers: &[u64]| {
+            let numbers = numbers.iter().map(|n| format!("{n:>width$}")).join("");
+            format!("{label:<9}{numbers}")
+        };
+        return format!(
+            "{}\n{}",
+            line("Time:", &times),
+            line("Distance:", &distances)
+        );
+    }
+}
+
+/// Distinct hands, each drawn from a few ranks so that all types occur.
+fn day07(rng: &mut ChaCha8Rng, hands: usize) -> String {
+    let mut seen = HashSet::new();
+    let mut lines = Vec::new();
+    while lines.len() < hands {
+        let rank_count = rng.gen_range(1..=5);
+        let ranks = b"23456789TJQKA"
+            .choose_multiple(rng, rank_count)
+            .copied()
+            .collect_vec();
+        let hand = letters(rng, 5, &ranks);
+        if seen.insert(hand.clone()) {
+            lines.push(format!("{hand} {}", rng.gen_range(1..=1000)));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Every ghost walks a cycle through its `..Z` node whose length is a prime
+/// between 41 and 97, distinct for each ghost. Between the start and the end
+/// there are two nodes at each step, which of them a ghost is on depends on
+/// the instructions.
+fn day08(rng: &mut ChaCha8Rng, ghosts: usize) -> String {
+    let mut periods = (41..100).filter(|&n| is_prime(n)).collect_vec();
+    periods.shuffle(rng);
+
+    let mut taken = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut nodes = Vec::new();
+    for (ghost, &period) in periods[..ghosts].iter().enumerate() {
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (
+                fresh_name(rng, &mut taken, |rng| letters(rng, 2, UPPER) + "A"),
+                fresh_name(rng, &mut taken, |rng| letters(rng, 2, UPPER) + "Z"),
+            ),
+        };
+        let mut steps = vec![vec![start]];
+        for _ in 1..period {
+            steps.push(
+                (0..2)
+                    .map(|_| {
+                        fresh_name(rng, &mut taken, |rng| {
+                            letters(rng, 2, UPPER) + &letters(rng, 1, b"BCDEFGHIJKLMNOPQRSTUVWXY")
+                        })
+                    })
+                    .collect(),
+            );
+        }
+        steps.push(vec![end.clone()]);
+
+        let mut start_targets = None;
+        for (step, next) in steps.iter().tuple_windows() {
+            for node in step {
+                let mut targets = [next[0].clone(), next[next.len() - 1].clone()];
+                targets.shuffle(rng);
+                start_targets.get_or_insert_with(|| targets.clone());
+                nodes.push((node.clone(), targets));
+            }
+        }
+        // The end leads on like the start, so the ghost is back on it every
+        // `period` steps.
+        nodes.push((end, start_targets.unwrap()));
+    }
+    nodes.shuffle(rng);
+
+    let len = rng.gen_range(200..=300);
+    let instructions = letters(rng, len, b"LR");
+    let nodes = nodes
+        .into_iter()
+        .map(|(node, [left, right])| format!("{node} = ({left}, {right})"))
+        .join("\n");
+    format!("{instructions}\n\n{nodes}")
+}
+
+/// Histories of 21 values of polynomials of degree up to 10.
+fn day09(rng: &mut ChaCha8Rng, histories: usize) -> String {
+    (0..histories)
+        .map(|_| {
+            // Sum up the differences, starting from constant ones.
+            let mut values = vec![rng.gen_range(-5..=5i64); 21];
+            for _ in 0..rng.gen_range(0..=10) {
+                let first = rng.gen_range(-20..=20);
+                values = values
+                    .iter()
+                    .scan(first, |value, &difference| {
+                        let current = *value;
+                        *value += difference;
+                        Some(current)
+                    })
+                    .collect();
+            }
+            values.iter().join(" ")
+        })
+        .join("\n")
+}
+
+/// The loop is the outline of a random tree, surrounded by junk pipes.
+fn day10(rng: &mut ChaCha8Rng, side: usize) -> String {
+    const BLOCK: usize = 3;
+    let corners = outline(&tree_region(rng, side / (BLOCK + 1), BLOCK, 0.6));
+    let dx = rng.gen_range(0..=side - corners.width());
+    let dy = rng.gen_range(0..=side - corners.height());
+
+    let mut map = Grid::from_vec(
+        side,
+        (0..side * side)
+            .map(|_| *b"|-LJ7F...".choose(rng).unwrap())
+            .collect(),
+    );
+    let mut on_loop = Grid::new(side, side, false);
+    let mut starts = Vec::new();
+    for (x, y) in corners.positions() {
+        let position = (x + dx, y + dy);
+        map[position] = match corners[(x, y)] {
+            0 => continue,
+            directions if directions == NORTH | SOUTH => b'|',
+            directions if directions == EAST | WEST => b'-',
+            directions if directions == NORTH | EAST => b'L',
+            directions if directions == NORTH | WEST => b'J',
+            directions if directions == SOUTH | WEST => b'7',
+            _ => b'F',
+        };
+        on_loop[position] = true;
+        starts.push(position);
+    }
+
+    let start = *starts.choose(rng).unwrap();
+    map[start] = b'S';
+    // Only the loop may connect to the start.
+    for next in map.neighbors_4(start.0, start.1).collect_vec() {
+        if !on_loop[next] {
+            map[next] = b'.';
+        }
+    }
+    render(&map)
+}
+
+/// An image with a few empty rows and columns.
+fn day11(rng: &mut ChaCha8Rng, side: usize) -> String {
+    let mut empty = |_| (0..side).map(|_| rng.gen_bool(0.05)).collect_vec();
+    let (empty_rows, empty_columns) = (empty(()), empty(()));
+    let mut image = Grid::new(side, side, b'.');
+    for (x, y) in image.positions().collect_vec() {
+        if !empty_rows[y] && !empty_columns[x] && rng.gen_bool(0.03) {
+            image[(x, y)] = b'#';
+        }
+    }
+    render(&image)
+}
+
+/// Rows of springs hiding an arrangement with at least one damaged spring.
+fn day12(rng: &mut ChaCha8Rng, rows: usize) -> String {
+    (0..rows)
+        .map(|_| {
+            let len = rng.gen_range(1..=20);
+            let mut damaged = (0..len).map(|_| rng.gen_bool(0.5)).collect_vec();
+            if !damaged.contains(&true) {
+                damaged[rng.gen_range(0..len)] = true;
+            }
+            let groups = damaged
+                .split(|&damaged| !damaged)
+                .filter(|group| !group.is_empty())
+                .map(<[bool]>::len)
+                .join(",");
+
+            let unknown = rng.gen_range(0.2..0.7);
+            let springs = damaged
+                .iter()
+                .map(|&damaged| match (rng.gen_bool(unknown), damaged) {
+                    (true, _) => '?',
+                    (false, true) => '#',
+                    (false, false) => '.',
+                })
+                .collect::<String>();
+            format!("{springs} {groups}")
+        })
+        .join("\n")
+}
+
+fn day13(rng: &mut ChaCha8Rng, patterns: usize) -> String {
+    (0..patterns)
+        .map(|_| loop {
+            if let Some(pattern) = mirrored_pattern(rng) {
+                break pattern;
+            }
+        })
+        .join("\n\n")
+}
+
+/// Number of lines of reflection of `lines` with exactly `smudges` cells
+/// that don't match.
+fn reflections(lines: &[u32], smudges: u32) -> usize {
+    (1..lines.len())
+        .filter(|&center| {
+            let (before, after) = lines.split_at(center);
+            izip!(before.iter().rev(), after)
+                .map(|(a, b)| (a ^ b).count_ones())
+                .sum::<u32>()
+                == smudges
+        })
+        .count()
+}
+
+/// A pattern with exactly one line of reflection and exactly one line that
+/// reflects but for a smudge, `None` if the random pattern has more.
+fn mirrored_pattern(rng: &mut ChaCha8Rng) -> Option<String> {
+    let (width, height) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+    // The mirror between the columns `column - 1` and `column` and the
+    // smudged one between the rows `row - 1` and `row`.
+    let (column, row) = (rng.gen_range(1..width), rng.gen_range(1..height));
+    let (column_reach, row_reach) = (column.min(width - column), row.min(height - row));
+    // The smudge has to be in a column the mirror doesn't reflect.
+    let smudge_columns = (0..width)
+        .filter(|x| !(column - column_reach..column + column_reach).contains(x))
+        .collect_vec();
+    let &smudge_x = smudge_columns.choose(rng)?;
+    let smudge_y = rng.gen_range(row - row_reach..row + row_reach);
+
+    let fold = |i: usize, mirror: usize, reach: usize| match (mirror..mirror + reach).contains(&i) {
+        true => 2 * mirror - 1 - i,
+        false => i,
+    };
+    let mut pattern = Grid::new(width, height, false);
+    for (x, y) in pattern.positions().collect_vec() {
+        let reflected = (fold(x, column, column_reach), fold(y, row, row_reach));
+        pattern[(x, y)] = match reflected == (x, y) {
+            true => rng.gen_bool(0.5),
+            false => pattern[reflected],
+        };
+    }
+    pattern[(smudge_x, smudge_y)] ^= true;
+
+    let rocks = |line: &mut dyn Iterator<Item = &bool>| {
+        line.enumerate()
+            .fold(0, |acc, (i, &rock)| acc | u32::from(rock) << i)
+    };
+    let rows = pattern
+        .rows()
+        .map(|row| rocks(&mut row.iter()))
+        .collect_vec();
+    let columns = pattern
+        .columns()
+        .map(|mut column| rocks(&mut column))
+        .collect_vec();
+    let count = |smudges| reflections(&rows, smudges) + reflections(&columns, smudges);
+    if count(0) != 1 || count(1) != 1 {
+        return None;
+    }
+
+    if rng.gen_bool(0.5) {
+        pattern = pattern.transpose();
+    }
+    Some(render(&pattern.map(|&rock| if rock { b'#' } else { b'.' })))
+}
+
+fn day14(rng: &mut ChaCha8Rng, side: usize) -> String {
+    let platform = (0..side * side)
+        .map(|_| match rng.gen_range(0..20) {
+            0..=3 => b'O',
+            4..=6 => b'#',
+            _ => b'.',
+        })
+        .collect();
+    render(&Grid::from_vec(side, platform))
+}
+
+/// Steps on a pool of labels, so lenses are replaced and removed again.
+fn day15(rng: &mut ChaCha8Rng, steps: usize) -> String {
+    let labels = (0..(steps / 8).max(1))
+        .map(|_| {
+            let len = rng.gen_range(2..=6);
+            letters(rng, len, LOWER)
+        })
+        .collect_vec();
+    (0..steps)
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            match rng.gen_bool(0.6) {
+                true => format!("{label}={}", rng.gen_range(1..=9)),
+                false => format!("{label}-"),
+            }
+        })
+        .join(",")
+}
+
+fn day16(rng: &mut ChaCha8Rng, side: usize) -> String {
+    let contraption = (0..side * side)
+        .map(|_| match rng.gen_bool(0.1) {
+            true => *br"|-/\".choose(rng).unwrap(),
+            false => b'.',
+        })
+        .collect();
+    render(&Grid::from_vec(side, contraption))
+}
+
+fn day17(rng: &mut ChaCha8Rng, side: usize) -> String {
+    let map = (0..side * side)
+        .map(|_| rng.gen_range(b'1'..=b'9'))
+        .collect();
+    render(&Grid::from_vec(side, map))
+}
+
+/// The trench is the outline of a random tree. The hidden plan digs the same
+/// shape, mirrored or rotated, to a much larger scale.
+fn day18(rng: &mut ChaCha8Rng, side: usize) -> String {
+    let corners = outline(&tree_region(rng, side, 1, 0.5));
+
+    // The corners where the trench turns, in the order they are dug.
+    let start = corners
+        .position(|&directions| {
+            directions != 0 && directions != NORTH | SOUTH && directions != EAST | WEST
+        })
+        .unwrap();
+    let mut turns = vec![[start.0 as i64, start.1 as i64]];
+    let mut direction = corners[start] & corners[start].wrapping_neg();
+    let mut position = start;
+    loop {
+        position = corners.step(position, offset(direction)).unwrap();
+        if position == start {
+            break;
+        }
+        let directions = corners[position];
+        if directions & direction == 0 {
+            turns.push([position.0 as i64, position.1 as i64]);
+            direction = directions & !opposite(direction);
+        }
+    }
+
+    let plain = dig_plan(rng, &turns, 5);
+    // Keeps every hex number of the hidden plan below 16⁵.
+    let max_step = ((1 << 20) - 1) / (corners.width() as i64);
+    let (swap, flip_x, flip_y) = (rng.gen_bool(0.5), rng.gen_bool(0.5), rng.gen_bool(0.5));
+    let mut hidden_turns = turns
+        .iter()
+        .map(|&[x, y]| {
+            let [x, y] = if swap { [y, x] } else { [x, y] };
+            [if flip_x { -x } else { x }, if flip_y { -y } else { y }]
+        })
+        .collect_vec();
+    if rng.gen_bool(0.5) {
+        hidden_turns.reverse();
+    }
+    let shift = rng.gen_range(0..hidden_turns.len());
+    hidden_turns.rotate_left(shift);
+    let hidden = dig_plan(rng, &hidden_turns, max_step);
+
+    izip!(plain, hidden)
+        .map(|((direction, steps), (hidden_direction, hidden_steps))| {
+            let direction = char::from(b"RDLU"[usize::from(direction)]);
+            format!("{direction} {steps} (#{hidden_steps:05x}{hidden_direction})")
+        })
+        .join("\n")
+}
+
+/// The digs from turn to turn, as an index into `RDLU` and a length, after
+/// stretching each unit of the coordinates to between 1 and `max_step`.
+fn dig_plan(rng: &mut ChaCha8Rng, turns: &[[i64; 2]], max_step: i64) -> Vec<(u8, i64)> {
+    let scales = [0, 1].map(|axis| {
+        let min = turns.iter().map(|turn| turn[axis]).min().unwrap();
+        let max = turns.iter().map(|turn| turn[axis]).max().unwrap();
+        let mut position = 0;
+        (min..=max)
+            .map(|coordinate| {
+                let scaled = (coordinate, position);
+                position += rng.gen_range(1..=max_step);
+                scaled
+            })
+            .collect::<BTreeMap<_, _>>()
+    });
+    let scaled = |turn: [i64; 2]| [0, 1].map(|axis| scales[axis][&turn[axis]]);
+
+    turns
+        .iter()
+        .circular_tuple_windows()
+        .map(|(&from, &to)| {
+            let ([x0, y0], [x1, y1]) = (scaled(from), scaled(to));
+            match (x1 - x0, y1 - y0) {
+                (dx, 0) if dx > 0 => (0, dx),
+                (0, dy) if dy > 0 => (1, dy),
+                (dx, 0) => (2, -dx),
+                (_, dy) => (3, -dy),
+            }
+        })
+        .collect()
+}
+
+/// Workflows forming a tree from `in`, so every rating combination ends up
+/// in exactly one leaf, and parts to sort.
+fn day19(rng: &mut ChaCha8Rng, workflows: usize) -> String {
+    let mut taken = HashSet::from(["in".to_string()]);
+    let mut queue = VecDeque::from([("in".to_string(), [(1u16, 4000u16); 4])]);
+    let mut created = 1;
+    let mut lines = Vec::new();
+    while let Some((name, mut bounds)) = queue.pop_front() {
+        let mut new_target = |rng: &mut ChaCha8Rng,
+                              bounds: [(u16, u16); 4],
+                              queue: &mut VecDeque<_>,
+                              force: bool| {
+            if created < workflows && (force || rng.gen_bool(0.6)) {
+                created += 1;
+                let name = fresh_name(rng, &mut taken, |rng| {
+                    let len = rng.gen_range(2..=3);
+                    letters(rng, len, LOWER)
+                });
+                queue.push_back((name.clone(), bounds));
+                name
+            } else {
+                ["A", "R"].choose(rng).unwrap().to_string()
+            }
+        };
+
+        let mut rules = Vec::new();
+        for _ in 0..rng.gen_range(1..=4) {
+            // Only split ratings that can still go either way.
+            let Some(attr) = (0..4)
+                .filter(|&attr| bounds[attr].0 < bounds[attr].1)
+                .choose(rng)
+            else {
+                break;
+            };
+            let (min, max) = bounds[attr];
+            let mut matching = bounds;
+            let (op, threshold) = match rng.gen_bool(0.5) {
+                true => {
+                    let threshold = rng.gen_range(min..max);
+                    matching[attr].0 = threshold + 1;
+                    bounds[attr].1 = threshold;
+                    ('>', threshold)
+                }
+                false => {
+                    let threshold = rng.gen_range(min + 1..=max);
+                    matching[attr].1 = threshold - 1;
+                    bounds[attr].0 = threshold;
+                    ('<', threshold)
+                }
+            };
+            let target = new_target(rng, matching, &mut queue, false);
+            rules.push(format!(
+                "{}{op}{threshold}:{target}",
+                char::from(b"xmas"[attr])
+            ));
+        }
+        // Keep going until there are enough workflows.
+        let force = queue.is_empty();
+        rules.push(new_target(rng, bounds, &mut queue, force));
+        lines.push(format!("{name}{{{}}}", rules.join(",")));
+    }
+    lines.shuffle(rng);
+
+    let parts = (0..200)
+        .map(|_| {
+            let [x, m, a, s] = [(); 4].map(|()| rng.gen_range(1..=4000));
+            format!("{{x={x},m={m},a={a},s={s}}}")
+        })
+        .join("\n");
+    format!("{}\n\n{parts}", lines.join("\n"))
+}
+
+/// Every counter is a chain of 12 flip-flops feeding a conjunction, the hub,
+/// which resets the counter when it reaches its period and then pulses an
+/// inverter feeding the conjunction in front of `rx`.
+fn day20(rng: &mut ChaCha8Rng, counters: usize) -> String {
+    const BITS: usize = 12;
+    let mut periods = (1 << (BITS - 1)..1 << BITS)
+        .filter(|&n| is_prime(n))
+        .choose_multiple(rng, counters);
+    periods.shuffle(rng);
+
+    let mut taken = HashSet::from(["rx".to_string()]);
+    let mut name = |rng: &mut ChaCha8Rng| fresh_name(rng, &mut taken, |rng| letters(rng, 2, LOWER));
+    let output = name(rng);
+    let mut modules = vec![format!("&{output} -> rx")];
+    let mut starts = Vec::new();
+    for period in periods {
+        let bits = (0..BITS).map(|_| name(rng)).collect_vec();
+        let (hub, inverter) = (name(rng), name(rng));
+        for (i, bit) in bits.iter().enumerate() {
+            let mut outputs = bits.get(i + 1).into_iter().collect_vec();
+            if period >> i & 1 == 1 {
+                outputs.push(&hub);
+            }
+            outputs.shuffle(rng);
+            modules.push(format!("%{bit} -> {}", outputs.iter().join(", ")));
+        }
+        let mut outputs = bits
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i == 0 || period >> i & 1 == 0)
+            .map(|(_, bit)| bit)
+            .chain([&inverter])
+            .collect_vec();
+        outputs.shuffle(rng);
+        modules.push(format!("&{hub} -> {}", outputs.iter().join(", ")));
+        modules.push(format!("&{inverter} -> {output}"));
+        starts.push(bits[0].clone());
+    }
+    modules.push(format!("broadcaster -> {}", starts.join(", ")));
+    modules.shuffle(rng);
+    modules.join("\n")
+}
+
+fn day21(rng: &mut ChaCha8Rng, side: usize) -> String {
+    let half = side / 2;
+    let mut garden = Grid::new(side, side, b'.');
+    for (x, y) in garden.positions().collect_vec() {
+        let free = [x, y].iter().any(|&c| c == 0 || c == side - 1 || c == half);
+        if !free && rng.gen_bool(0.15) {
+            garden[(x, y)] = b'#';
+        }
+    }
+    garden[(half, half)] = b'S';
+    render(&garden)
+}
+
+/// Bricks on a 10 by 10 floor, dropped from a bit above where they land.
+/// Each brick ends at most seven above the highest one so far, which keeps
+/// even the largest inputs below the height day 22 accepts.
+fn day22(rng: &mut ChaCha8Rng, bricks: usize) -> String {
+    let mut heights = Grid::new(10, 10, 0);
+    let mut lines = (0..bricks)
+        .map(|_| {
+            let mut size = [1; 3];
+            size[rng.gen_range(0..3)] = rng.gen_range(1..=4);
+            let x = rng.gen_range(0..=10 - size[0]);
+            let y = rng.gen_range(0..=10 - size[1]);
+            let below = (x..x + size[0])
+                .flat_map(|x| (y..y + size[1]).map(move |y| (x, y)))
+                .map(|position| heights[position])
+                .max()
+                .unwrap();
+            let z = below + 1 + rng.gen_range(0..=3);
+            for x in x..x + size[0] {
+                for y in y..y + size[1] {
+                    heights[(x, y)] = z + size[2] - 1;
+                }
+            }
+            let (x1, y1, z1) = (x + size[0] - 1, y + size[1] - 1, z + size[2] - 1);
+            format!("{x},{y},{z}~{x1},{y1},{z1}")
+        })
+        .collect_vec();
+    lines.shuffle(rng);
+    lines.join("\n")
+}
+
+/// Junctions on a square lattice joined by winding trails to their right and
+/// below, with slopes that only allow walking right and down.
+fn day23(rng: &mut ChaCha8Rng, junctions: usize) -> String {
+    // Trails wind up to `BAND` cells away from the straight line between two
+    // junctions, so the trails of neighbouring junctions never touch.
+    const PITCH: usize = 22;
+    const BAND: usize = 4;
+    let first = BAND + 3;
+    let last = first + (junctions - 1) * PITCH;
+    let side = last + first + 1;
+
+    let mut map = Grid::new(side, side, b'#');
+    let dig = |map: &mut Grid<u8>, from: (usize, usize), to: (usize, usize)| {
+        for y in from.1.min(to.1)..=from.1.max(to.1) {
+            for x in from.0.min(to.0)..=from.0.max(to.0) {
+                map[(x, y)] = b'.';
+            }
+        }
+    };
+    dig(&mut map, (1, 0), (1, 1));
+    dig(&mut map, (1, 1), (first, 1));
+    dig(&mut map, (first, 1), (first, first));
+    dig(&mut map, (last, last), (last, side - 2));
+    dig(&mut map, (last, side - 2), (side - 2, side - 2));
+    dig(&mut map, (side - 2, side - 2), (side - 2, side - 1));
+
+    for j in 0..junctions {
+        for i in 0..junctions {
+            let (x, y) = (first + i * PITCH, first + j * PITCH);
+            // The trails to the right and below, drawn along the first axis
+            // and winding along the second.
+            for (swap, slope) in [(false, b'>'), (true, b'v')] {
+                if (if swap { j } else { i }) + 1 == junctions {
+                    continue;
+                }
+                let at = |along: usize, across: usize| match swap {
+                    false => (along, across),
+                    true => (across, along),
+                };
+                let (along, across) = if swap { (y, x) } else { (x, y) };
+                let end = along + PITCH;
+                let mut offset = across;
+                dig(&mut map, at(along, across), at(along + BAND + 2, across));
+                for step in (along + BAND + 2..end - BAND - 2).step_by(2) {
+                    let next = match step + 2 == end - BAND - 2 {
+                        true => across,
+                        false => rng.gen_range(across - BAND..=across + BAND),
+                    };
+                    dig(&mut map, at(step, offset), at(step, next));
+                    dig(&mut map, at(step, next), at(step + 2, next));
+                    offset = next;
+                }
+                dig(&mut map, at(end - BAND - 2, across), at(end, across));
+                map[at(along + 1, across)] = slope;
+                map[at(end - 1, across)] = slope;
+            }
+        }
+    }
+    render(&map)
+}
+
+/// Hailstones that a rock thrown from an integer position hits at distinct
+/// integer times.
+fn day24(rng: &mut ChaCha8Rng, hailstones: usize) -> String {
+    let position = [(); 3].map(|()| rng.gen_range(200_000_000_000_000..=400_000_000_000_000i64));
+    let velocity = [(); 3].map(|()| rng.gen_range(-300..=300i64));
+    let times = distinct_sorted(rng, hailstones, 1_000_000_000..300_000_000_000);
+    let mut lines = times
+        .into_iter()
+        .map(|time| {
+            let hailstone_velocity = loop {
+                let v = [(); 3].map(|()| rng.gen_range(-300..=300i64));
+                if v != velocity {
+                    break v;
+                }
+            };
+            let hailstone_position = [0, 1, 2]
+                .map(|i| position[i] + time as i64 * (velocity[i] - hailstone_velocity[i]));
+            format!(
+                "{} @ {}",
+                hailstone_position.iter().join(", "),
+                hailstone_velocity.iter().join(", ")
+            )
+        })
+        .collect_vec();
+    lines.shuffle(rng);
+    lines.join("\n")
+}
+
+/// Two groups of components, each wired well enough that no three wires
+/// split it, joined by three wires.
+fn day25(rng: &mut ChaCha8Rng, components: usize) -> String {
+    let mut taken = HashSet::new();
+    let names = (0..components)
+        .map(|_| fresh_name(rng, &mut taken, |rng| letters(rng, 3, LOWER)))
+        .collect_vec();
+    let split = rng
+        .gen_range(2 * components / 5..=3 * components / 5)
+        .clamp(5, components - 5);
+
+    let mut wires = BTreeSet::new();
+    let mut wire = |a: usize, b: usize| wires.insert((a.min(b), a.max(b)));
+    let mut sides = [(0..split).collect_vec(), (split..components).collect_vec()];
+    for side in &mut sides {
+        // Every component is wired to the two before and after it in a
+        // random cycle, which takes cutting four wires to split.
+        side.shuffle(rng);
+        let n = side.len();
+        for i in 0..n {
+            wire(side[i], side[(i + 1) % n]);
+            wire(side[i], side[(i + 2) % n]);
+        }
+        for _ in 0..n / 2 {
+            let (a, b) = (*side.choose(rng).unwrap(), *side.choose(rng).unwrap());
+            if a != b {
+                wire(a, b);
+            }
+        }
+    }
+    let [left, right] = &sides;
+    for (&a, &b) in izip!(
+        left.choose_multiple(rng, 3).collect_vec(),
+        right.choose_multiple(rng, 3).collect_vec()
+    ) {
+        wire(a, b);
+    }
+
+    // Each wire is listed under one of its ends.
+    let mut listed = BTreeMap::<_, Vec<_>>::new();
+    for (a, b) in wires {
+        let (owner, other) = if rng.gen_bool(0.5) { (a, b) } else { (b, a) };
+        listed.entry(owner).or_default().push(other);
+    }
+    let mut lines = listed
+        .into_iter()
+        .map(|(owner, others)| {
+            format!(
+                "{}: {}",
+                names[owner],
+                others.iter().map(|&other| &names[other]).join(" ")
+            )
+        })
+        .collect_vec();
+    lines.shuffle(rng);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{puzzle, Solution};
+
+    #[test]
+    fn test_generated_inputs_run() {
+        for generator in &GENERATORS {
+            let size = *generator.sizes[0].start().max(&3);
+            let input = generator.generate(size, 1).unwrap();
+            let result = puzzle(generator.day).unwrap().run(input.as_bytes(), None);
+            assert!(result.is_ok(), "day {}: {result:?}", generator.day);
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_input() {
+        for generator in &GENERATORS {
+            let size = generator.default_size;
+            assert_eq!(
+                generator.generate(size, 7),
+                generator.generate(size, 7),
+                "day {}",
+                generator.day
+            );
+        }
+    }
+
+    #[test]
+    fn test_day08_periods() {
+        let input = generator(8).unwrap().generate(3, 2).unwrap();
+        let part_1 = crate::day08::part_1(input.as_bytes()).unwrap().unwrap();
+        let part_2 = crate::day08::part_2(input.as_bytes()).unwrap().unwrap();
+        assert!(is_prime(part_1));
+        assert_eq!(part_2 % part_1, 0);
+    }
+
+    #[test]
+    fn test_day20_period() {
+        let input = generator(20).unwrap().generate(1, 3).unwrap();
+        let presses = crate::day20::part_2(input.as_bytes()).unwrap().unwrap();
+        assert!(is_prime(presses) && (2049..4096).contains(&presses));
+    }
+
+    #[test]
+    fn test_day22_largest_input_parses() {
+        let generator = generator(22).unwrap();
+        let size = *generator.sizes.last().unwrap().end();
+        let input = generator.generate(size, 5).unwrap();
+        assert!(crate::day22::Day22::parse(input.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_day25_cut() {
+        let input = generator(25).unwrap().generate(40, 4).unwrap();
+        assert!(crate::day25::part_1(input.as_bytes()).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_size_error() {
+        let error = generator(21).unwrap().generate(100, 0).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 21 takes 3, 131 or 393 as the side of the map, not 100"
+        );
+    }
+}
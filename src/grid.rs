@@ -0,0 +1,305 @@
+//! A rectangular 2D grid, shared by the days that work on maps.
+
+use std::ops::{Index, IndexMut};
+
+use bstr::ByteSlice;
+
+use crate::parse::{ParseError, Parser};
+
+/// Offsets of the 4 orthogonal neighbours: up, right, down, left.
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// Offsets of the 8 neighbours, orthogonal and diagonal.
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid stored row by row, indexed by `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width` by `height` cells all set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Grid of the `cells` in row order.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is zero or the number of cells is not a multiple of it.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(width > 0, "a grid has to be at least one cell wide");
+        assert!(
+            cells.len().is_multiple_of(width),
+            "cells don't form a rectangle"
+        );
+        Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Parses a rectangular grid of the `allowed` bytes, converting each of
+    /// them with `f`. See [`Parser::grid`].
+    pub fn parse_with(
+        parser: &Parser,
+        input: &[u8],
+        allowed: &[u8],
+        f: impl FnMut(u8) -> T,
+    ) -> Result<Self, ParseError> {
+        let (width, _) = parser.grid(input, allowed)?;
+        Ok(Self::from_vec(
+            width,
+            input.lines().flatten().copied().map(f).collect(),
+        ))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// All cells in row order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// The cell at `(x, y)` of the grid repeated infinitely in all directions.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self.cells[y * self.width + x]
+    }
+
+    /// The position `(dx, dy)` away from `(x, y)`, if it is inside the grid.
+    pub fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// Orthogonal neighbours of `(x, y)` inside the grid.
+    pub fn neighbors_4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |offset| self.step((x, y), offset))
+    }
+
+    /// Orthogonal and diagonal neighbours of `(x, y)` inside the grid.
+    pub fn neighbors_8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |offset| self.step((x, y), offset))
+    }
+
+    /// All positions in row order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height).flat_map(|y| (0..self.width).map(move |x| (x, y)))
+    }
+
+    /// Position of the first cell, in row order, matching `predicate`.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let i = self.cells.iter().position(predicate)?;
+        Some((i % self.width, i / self.width))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.cells.chunks_exact(self.width)
+    }
+
+    pub fn rows_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut [T]> + ExactSizeIterator {
+        self.cells.chunks_exact_mut(self.width)
+    }
+
+    /// The cells of column `x`, top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// If `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "x = {x} is out of bounds");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Applies `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The grid mirrored along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// The grid rotated by 90° clockwise.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flat_map(Iterator::rev).cloned().collect(),
+        }
+    }
+
+    /// The grid rotated by 90° counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .rev()
+                .flat_map(|x| self.column(x))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a rectangular grid of the `allowed` bytes, see [`Parser::grid`].
+    pub fn parse(parser: &Parser, input: &[u8], allowed: &[u8]) -> Result<Self, ParseError> {
+        Self::parse_with(parser, input, allowed, |c| c)
+    }
+
+    /// Parses a rectangular grid of arbitrary bytes, see [`Parser::rectangle`].
+    pub fn parse_any(parser: &Parser, input: &[u8]) -> Result<Self, ParseError> {
+        let (width, _) = parser.rectangle(input)?;
+        Ok(Self::from_vec(
+            width,
+            input.lines().flatten().copied().collect(),
+        ))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width, "x = {x} is out of bounds");
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width, "x = {x} is out of bounds");
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T> FromIterator<Vec<T>> for Grid<T> {
+    /// Collects rows of equal length into a grid.
+    ///
+    /// # Panics
+    ///
+    /// If there are no rows, the rows are empty or they differ in length.
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(rows: I) -> Self {
+        let mut rows = rows.into_iter().peekable();
+        let width = rows.peek().map_or(0, Vec::len);
+        let cells = rows
+            .inspect(|row| assert_eq!(row.len(), width, "rows differ in length"))
+            .flatten()
+            .collect();
+        Self::from_vec(width, cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        let input = b"abc\ndef";
+        Grid::parse(&Parser::new(0, input), input, b"abcdef").unwrap()
+    }
+
+    #[test]
+    fn test_access() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], b'f');
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(*grid.get_wrapping(-1, 2), b'c');
+        assert_eq!(grid.step((0, 0), (-1, 0)), None);
+        assert_eq!(grid.position(|&c| c == b'e'), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid();
+        assert_eq!(grid.neighbors_4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors_8(1, 0).count(), 5);
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = grid();
+        assert_eq!(grid.row(1), b"def");
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"be");
+        // Past the last column, the cells of the next row would follow.
+        assert!(std::panic::catch_unwind(|| grid.column(3).count()).is_err());
+        assert_eq!(grid.transpose().cells(), b"adbecf");
+        assert_eq!(grid.rotate_clockwise().cells(), b"daebfc");
+        assert_eq!(grid.rotate_counterclockwise().cells(), b"cfbead");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
@@ -1,6 +1,390 @@
-#![feature(test)]
+//! Solutions to [Advent of Code 2023](https://adventofcode.com/2023).
+//!
+//! Every day lives in its own module, e.g. [`day05`], with `part_1` and
+//! `part_2` functions that take the raw puzzle input and return the answer, or
+//! a [`ParseError`] if the input is malformed.
+//! These, and the other public functions of the day modules, are the stable
+//! interface of this crate. Each module also has a unit struct implementing
+//! [`Solution`] which the binaries use to run the days generically.
+
+use std::{
+    env,
+    ffi::OsStr,
+    fmt, fs,
+    hint::black_box,
+    io::{self, Read},
+    path::Path,
+    process,
+};
 
 use mimalloc_rust::GlobalMiMalloc;
 
+pub use crate::{grid::Grid, parse::ParseError};
+
+/// Enters a tracing span until the end of the enclosing block, with the
+/// `trace` feature. Takes the arguments of [`tracing::info_span!`], without
+/// the feature they are not evaluated at all.
+macro_rules! enter_span {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "trace")]
+        let _span = tracing::info_span!($($arg)*).entered();
+    };
+}
+
+#[cfg(feature = "alloc-stats")]
+pub mod alloc_stats;
+pub mod answers;
+pub mod bench;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod generate;
+pub mod grid;
+pub mod math;
+pub mod output;
+pub mod parse;
+pub mod search;
+pub mod season;
+pub mod stream;
+pub mod viz;
+
+#[cfg(not(feature = "alloc-stats"))]
 #[global_allocator]
 static GLOBAL_MIMALLOC: GlobalMiMalloc = GlobalMiMalloc;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL_MIMALLOC: alloc_stats::Counting<GlobalMiMalloc> =
+    alloc_stats::Counting(GlobalMiMalloc);
+
+/// A day of the puzzle calendar.
+///
+/// Parsing is split from solving so both parts can share the parsed input.
+/// Days that solve straight from the raw bytes use `&[u8]` as their parsed
+/// type.
+pub trait Solution {
+    type Parsed<'a>;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    /// Day of December, `1..=25`.
+    const DAY: u8;
+    /// The input compiled into the crate with the `embed-input` feature.
+    const INPUT: Option<&'static [u8]> = None;
+
+    /// Parses the raw puzzle input, checking everything both parts rely on.
+    fn parse(input: &[u8]) -> Result<Self::Parsed<'_>, ParseError>;
+
+    /// Solves the first part of the puzzle.
+    fn part_1(parsed: &Self::Parsed<'_>) -> Self::Part1;
+
+    /// Solves the second part of the puzzle.
+    fn part_2(parsed: &Self::Parsed<'_>) -> Self::Part2;
+
+    /// Assumptions the solutions make about the input that `parsed` doesn't
+    /// meet. The input isn't malformed, but the answers are likely wrong.
+    fn warnings(_parsed: &Self::Parsed<'_>) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// The answer to one part of a puzzle.
+pub trait Answer {
+    /// Whether the part has a puzzle at all, see [`NoAnswer`].
+    const EXISTS: bool = true;
+    /// Name of the number type of the answer like `u64`, which tells how
+    /// large it can get.
+    const TYPE: &'static str;
+    /// Whether some inputs have no answer, see [`Option`].
+    const NULLABLE: bool = false;
+
+    /// The answer as it is entered on the puzzle page.
+    fn to_answer(&self) -> String;
+}
+
+macro_rules! impl_answer {
+    ($($ty:ty),*) => {
+        $(impl Answer for $ty {
+            const TYPE: &'static str = stringify!($ty);
+
+            fn to_answer(&self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+impl_answer!(u16, u32, u64, u128, usize, i64);
+
+/// Answer of a part that some valid inputs have none for, e.g. because it
+/// doesn't fit into `T`. A missing answer is `none`.
+impl<T: Answer> Answer for Option<T> {
+    const TYPE: &'static str = T::TYPE;
+    const NULLABLE: bool = true;
+
+    fn to_answer(&self) -> String {
+        self.as_ref()
+            .map_or_else(|| "none".to_string(), T::to_answer)
+    }
+}
+
+/// Answer type of a part without a puzzle, i.e. part 2 of day 25.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoAnswer;
+
+impl Answer for NoAnswer {
+    const EXISTS: bool = false;
+    const TYPE: &'static str = "none";
+
+    fn to_answer(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("-")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// Formatted answers of a single run, `None` for parts that were not run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Object safe view of a [`Solution`] so all days can be run from one table.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+
+    fn input(&self) -> Option<&'static [u8]>;
+
+    /// Runs `part`, or both parts if `part` is `None`.
+    fn run(&self, input: &[u8], part: Option<Part>) -> Result<Answers, ParseError>;
+
+    /// Times parsing and each part separately.
+    fn bench(
+        &self,
+        input: &[u8],
+        options: &bench::Options,
+    ) -> Result<Vec<bench::Measurement>, ParseError>;
+
+    /// Solves both parts once, timing parsing and each part.
+    fn time(&self, input: &[u8]) -> Result<season::Timing, ParseError>;
+
+    /// Counts the heap allocations of parsing and each part.
+    #[cfg(feature = "alloc-stats")]
+    fn alloc_stats(
+        &self,
+        input: &[u8],
+    ) -> Result<Vec<(bench::Stage, alloc_stats::AllocStats)>, ParseError>;
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn input(&self) -> Option<&'static [u8]> {
+        S::INPUT
+    }
+
+    fn run(&self, input: &[u8], part: Option<Part>) -> Result<Answers, ParseError> {
+        let parsed = {
+            enter_span!("parse", day = S::DAY);
+            S::parse(input)?
+        };
+        Ok(Answers {
+            part_1: (part != Some(Part::Two)).then(|| {
+                enter_span!("part 1", day = S::DAY);
+                S::part_1(&parsed).to_answer()
+            }),
+            part_2: (S::Part2::EXISTS && part != Some(Part::One)).then(|| {
+                enter_span!("part 2", day = S::DAY);
+                S::part_2(&parsed).to_answer()
+            }),
+        })
+    }
+
+    fn bench(
+        &self,
+        input: &[u8],
+        options: &bench::Options,
+    ) -> Result<Vec<bench::Measurement>, ParseError> {
+        use bench::{measure, Stage};
+
+        let parsed = S::parse(input)?;
+        let mut measurements = vec![
+            measure(S::DAY, Stage::Parse, options, || S::parse(black_box(input))),
+            measure(S::DAY, Stage::Part1, options, || {
+                S::part_1(black_box(&parsed))
+            }),
+        ];
+        if S::Part2::EXISTS {
+            measurements.push(measure(S::DAY, Stage::Part2, options, || {
+                S::part_2(black_box(&parsed))
+            }));
+        }
+        Ok(measurements)
+    }
+
+    fn time(&self, input: &[u8]) -> Result<season::Timing, ParseError> {
+        let (parsed, parse_ns) = season::timed(|| {
+            enter_span!("parse", day = S::DAY);
+            S::parse(input)
+        });
+        let parsed = parsed?;
+        let (_, part1_ns) = season::timed(|| {
+            enter_span!("part 1", day = S::DAY);
+            S::part_1(&parsed)
+        });
+        let part2_ns = S::Part2::EXISTS.then(|| {
+            season::timed(|| {
+                enter_span!("part 2", day = S::DAY);
+                S::part_2(&parsed)
+            })
+            .1
+        });
+        Ok(season::Timing {
+            parse_ns,
+            part1_ns,
+            part2_ns,
+        })
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    fn alloc_stats(
+        &self,
+        input: &[u8],
+    ) -> Result<Vec<(bench::Stage, alloc_stats::AllocStats)>, ParseError> {
+        use alloc_stats::measure;
+        use bench::Stage;
+
+        let (parsed, parse) = measure(|| S::parse(input));
+        let parsed = parsed?;
+        let mut stats = vec![
+            (Stage::Parse, parse),
+            (Stage::Part1, measure(|| S::part_1(&parsed)).1),
+        ];
+        if S::Part2::EXISTS {
+            stats.push((Stage::Part2, measure(|| S::part_2(&parsed)).1));
+        }
+        Ok(stats)
+    }
+}
+
+/// Every day of the calendar, `PUZZLES[0]` is day 1.
+pub static PUZZLES: [&dyn Puzzle; 25] = [
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+pub fn puzzle(day: u8) -> Option<&'static dyn Puzzle> {
+    PUZZLES.get(usize::from(day).checked_sub(1)?).copied()
+}
+
+/// Entry point of the `day-NN` binaries, see [`output::Args`] for their
+/// command line.
+///
+/// A malformed input is reported with an excerpt of the offending line and
+/// exits the process with status 1.
+pub fn day_main<S: Solution>() {
+    let args = <output::Args as clap::Parser>::parse();
+    let input = load_input(args.input.as_deref().map(Path::as_os_str), S::INPUT)
+        .expect("failed to read puzzle input");
+    let output = output::solve::<S>(&input);
+    output::write(
+        io::stdout().lock(),
+        io::stderr().lock(),
+        args.format,
+        &output,
+    )
+    .expect("failed to write answers");
+    if output.error.is_some() {
+        process::exit(1);
+    }
+}
+
+/// Reads the puzzle input of a program whose first command line argument is
+/// the path of the input file, see [`load_input`] for the rest.
+pub fn read_input(default: Option<&[u8]>) -> io::Result<Vec<u8>> {
+    load_input(env::args_os().nth(1).as_deref(), default)
+}
+
+/// Loads the puzzle input from `path`, `-` reads the input from stdin.
+/// Without a path the embedded `default` is used if there is one (see the
+/// `embed-input` feature), otherwise stdin.
+///
+/// A single trailing newline is stripped, so files saved by a browser behave
+/// the same as the embedded inputs.
+pub fn load_input(path: Option<&OsStr>, default: Option<&[u8]>) -> io::Result<Vec<u8>> {
+    let mut input = match (path, default) {
+        (Some(path), _) if path != "-" => fs::read(path)?,
+        (None, Some(default)) => return Ok(default.to_vec()),
+        _ => {
+            let mut buf = Vec::new();
+            io::stdin().lock().read_to_end(&mut buf)?;
+            buf
+        }
+    };
+
+    if input.ends_with(b"\n") {
+        input.pop();
+        if input.ends_with(b"\r") {
+            input.pop();
+        }
+    }
+    Ok(input)
+}
@@ -0,0 +1,237 @@
+//! Number theory and geometry helpers shared by the days.
+
+use std::ops::RangeInclusive;
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, `None` if it does not fit into a `u64`.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Greatest common divisor `g` of `a` and `b` together with Bézout
+/// coefficients `x` and `y`, so that `a * x + b * y == g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Smallest `x` with `x ≡ residue (mod modulus)` for all `congruences`,
+/// together with the least common multiple of the moduli.
+///
+/// The moduli don't have to be coprime. `None` if the congruences
+/// contradict each other or the combined modulus does not fit into a `u64`.
+/// Intermediate products are computed in `u128`, so they never overflow.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    congruences.iter().try_fold((0, 1), |(r1, m1), &(r2, m2)| {
+        if m2 == 0 {
+            return None;
+        }
+        let r2 = r2 % m2;
+        let g = gcd(m1, m2);
+        let diff = i128::from(r2) - i128::from(r1);
+        if diff % i128::from(g) != 0 {
+            return None;
+        }
+
+        let modulus = lcm(m1, m2)?;
+        let m2_g = u128::from(m2 / g);
+        // m1 / g is invertible modulo m2 / g.
+        let (_, inverse, _) = extended_gcd(i128::from(m1 / g), m2_g as i128);
+        let inverse = inverse.rem_euclid(m2_g as i128) as u128;
+        let diff = (diff / i128::from(g)).rem_euclid(m2_g as i128) as u128;
+        let t = diff * inverse % m2_g;
+        // r1 < m1 and t < m2 / g, so this is below the combined modulus.
+        let x = u128::from(r1) + u128::from(m1) * t;
+        Some((x as u64, modulus))
+    })
+}
+
+/// Twice the area of the simple polygon with the given `vertices`, by the
+/// shoelace formula. `None` if a term or their sum does not fit into an
+/// `i64`.
+pub fn shoelace(vertices: impl IntoIterator<Item = [i64; 2]>) -> Option<u64> {
+    let mut vertices = vertices.into_iter();
+    let Some(first) = vertices.next() else {
+        return Some(0);
+    };
+    let (last, sum) = vertices.try_fold((first, 0i64), |(from, sum), to| {
+        Some((to, sum.checked_add(shoelace_step(from, to)?)?))
+    })?;
+    Some(sum.checked_add(shoelace_step(last, first)?)?.unsigned_abs())
+}
+
+/// Term of the shoelace formula for the edge from `from` to `to`. Summed
+/// over the edges of a closed polygon, it is twice its signed area. `None`
+/// if it does not fit into an `i64`.
+pub fn shoelace_step([x0, y0]: [i64; 2], [x1, y1]: [i64; 2]) -> Option<i64> {
+    y0.checked_add(y1)?.checked_mul(x1.checked_sub(x0)?)
+}
+
+/// Number of lattice points strictly inside a polygon with lattice vertices,
+/// by Pick's theorem, from twice its area and the number of lattice points
+/// on its boundary.
+///
+/// The polygon has to be simple, for others the result is meaningless but
+/// doesn't underflow.
+pub fn interior_points(double_area: u64, boundary: u64) -> u64 {
+    (double_area + 2).saturating_sub(boundary) / 2
+}
+
+/// All integers `x` with `a * x² + b * x + c < 0`, computed without floating
+/// point. `a` has to be positive, `None` if there are no such integers or
+/// `4 * a * c` or the bounds of the range overflow.
+pub fn quadratic_below_zero(a: i64, b: i64, c: i64) -> Option<RangeInclusive<i64>> {
+    assert!(a > 0, "the parabola has to open upwards");
+    let (a, b, c) = (i128::from(a), i128::from(b), i128::from(c));
+    // Between the zeros, f is at least its minimum c - b² / 4a, which fits.
+    let f = |x: i128| (a * x + b) * x + c;
+
+    // b² fits into an i128, 4ac may not.
+    let discriminant = (b * b).checked_sub(a.checked_mul(c)?.checked_mul(4)?)?;
+    if discriminant <= 0 {
+        return None;
+    }
+    let root = discriminant.isqrt();
+    // Estimates of the zeros, off by at most one in each direction.
+    let mut low = (-b - root).div_euclid(2 * a);
+    let mut high = (-b + root).div_euclid(2 * a) + 1;
+    while f(low) >= 0 && low <= high {
+        low += 1;
+    }
+    while f(high) >= 0 && high >= low {
+        high -= 1;
+    }
+
+    if low > high {
+        return None;
+    }
+    Some(low.try_into().ok()?..=high.try_into().ok()?)
+}
+
+/// Value at `x` of the polynomial of lowest degree through `samples`, taken
+/// at `0, 1, 2, …`, by Newton's forward differences.
+///
+/// # Panics
+///
+/// If the result does not fit into an `i64`, see [`checked_extrapolate`].
+pub fn extrapolate(samples: &[i64], x: i64) -> i64 {
+    checked_extrapolate(samples, x).expect("extrapolated value overflows i64")
+}
+
+/// Like [`extrapolate`], but `None` if the result does not fit into an `i64`.
+pub fn checked_extrapolate(samples: &[i64], x: i64) -> Option<i64> {
+    let mut differences = samples.iter().map(|&s| i128::from(s)).collect::<Vec<_>>();
+    let x = i128::from(x);
+    let mut result = 0i128;
+    // x choose k, exact for negative x as well.
+    let mut binomial = 1i128;
+    for k in 0.. {
+        if differences.iter().all(|&d| d == 0) {
+            break;
+        }
+        result = result.checked_add(binomial.checked_mul(differences[0])?)?;
+        binomial = binomial.checked_mul(x - k)? / (k + 1);
+        for i in 1..differences.len() {
+            differences[i - 1] = differences[i].checked_sub(differences[i - 1])?;
+        }
+        differences.pop();
+    }
+    result.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        let (g, x, y) = extended_gcd(-35, 15);
+        assert_eq!((g, -35 * x + 15 * y), (5, 5));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+
+        let (a, b) = (1 << 40, (1 << 23) - 1);
+        let (x, modulus) = crt(&[(5, a), (7, b)]).unwrap();
+        assert_eq!((x % a, x % b, modulus), (5, 7, a * b));
+        assert_eq!(crt(&[(1, u64::MAX), (0, 2)]), None);
+    }
+
+    #[test]
+    fn test_shoelace_pick() {
+        let square = [[0, 0], [4, 0], [4, 4], [0, 4]];
+        assert_eq!(shoelace(square), Some(32));
+        assert_eq!(shoelace(square.into_iter().rev()), Some(32));
+        assert_eq!(interior_points(32, 16), 9);
+        assert_eq!(shoelace([]), Some(0));
+
+        let edges = square.into_iter().zip(square.into_iter().cycle().skip(1));
+        let steps = edges.map(|(from, to)| shoelace_step(from, to).unwrap());
+        assert_eq!(steps.sum::<i64>(), -32);
+
+        let huge = 1 << 32;
+        assert_eq!(shoelace([[0, 0], [huge, 0], [huge, huge], [0, huge]]), None);
+        assert_eq!(shoelace_step([0, i64::MAX], [1, 1]), None);
+    }
+
+    #[test]
+    fn test_quadratic_below_zero() {
+        // (x - 2) * (x - 5) = x² - 7x + 10
+        assert_eq!(quadratic_below_zero(1, -7, 10), Some(3..=4));
+        assert_eq!(quadratic_below_zero(1, -7, 9), Some(2..=5));
+        assert_eq!(quadratic_below_zero(1, 0, 0), None);
+        assert_eq!(quadratic_below_zero(2, 0, -1), Some(0..=0));
+        assert_eq!(quadratic_below_zero(1, i64::MIN, i64::MAX), Some(2..=i64::MAX - 1));
+        // 4ac overflows, or the zeros are beyond the range of an i64.
+        assert_eq!(quadratic_below_zero(i64::MAX, 0, i64::MIN), None);
+        assert_eq!(quadratic_below_zero(1, i64::MIN, i64::MIN), None);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(extrapolate(&[0, 3, 6, 9, 12, 15], 6), 18);
+        assert_eq!(extrapolate(&[1, 3, 6, 10, 15, 21], 6), 28);
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -1), 5);
+        assert_eq!(extrapolate(&[7], 100), 7);
+        assert_eq!(
+            extrapolate(&[3, 5, 9], 202_300),
+            202_300 * 202_300 + 202_300 + 3
+        );
+    }
+}
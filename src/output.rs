@@ -0,0 +1,185 @@
+//! What the `day-NN` binaries print: the answers as text, or with
+//! `--format json` one JSON document for scripts.
+
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use serde::Serialize;
+
+use crate::{season::timed, Answer, Solution};
+
+/// Command line of the `day-NN` binaries.
+#[derive(Debug, clap::Parser)]
+#[command(about = "Solves both parts of the day")]
+pub struct Args {
+    /// Input file, `-` reads stdin; defaults to the embedded input if there
+    /// is one, otherwise stdin
+    pub input: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// `Part 1: …` lines, warnings and errors go to stderr
+    Text,
+    /// One document with the answers, their timings, warnings and errors
+    Json,
+}
+
+/// Everything a day binary reports about solving an input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayOutput {
+    pub day: u8,
+    pub parse_ns: u64,
+    /// The parts that were solved, none if the input is malformed.
+    pub parts: Vec<PartOutput>,
+    /// Assumptions of the solutions that the input doesn't meet, see
+    /// [`Solution::warnings`].
+    pub warnings: Vec<String>,
+    pub error: Option<ErrorOutput>,
+}
+
+/// The answer to one part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PartOutput {
+    pub part: u8,
+    pub answer: String,
+    /// Number type of the answer like `u64`, which tells how large it can
+    /// get, see [`Answer::TYPE`].
+    #[serde(rename = "type")]
+    pub answer_type: &'static str,
+    /// Whether the part may have no answer, `answer` is `none` then.
+    pub nullable: bool,
+    pub time_ns: u64,
+}
+
+/// A malformed input, see [`ParseError`](crate::ParseError).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorOutput {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    /// The error with an excerpt of the offending line, as printed in text.
+    pub report: String,
+}
+
+/// Parses `input` and solves both parts of `S`, timing each stage.
+pub fn solve<S: Solution>(input: &[u8]) -> DayOutput {
+    fn part<A: Answer>(part: u8, (answer, time_ns): (A, u64)) -> PartOutput {
+        PartOutput {
+            part,
+            answer: answer.to_answer(),
+            answer_type: A::TYPE,
+            nullable: A::NULLABLE,
+            time_ns,
+        }
+    }
+
+    let (parsed, parse_ns) = timed(|| S::parse(input));
+    let mut output = DayOutput {
+        day: S::DAY,
+        parse_ns,
+        parts: Vec::new(),
+        warnings: Vec::new(),
+        error: None,
+    };
+    match parsed {
+        Ok(parsed) => {
+            output.warnings = S::warnings(&parsed);
+            output.parts.push(part(1, timed(|| S::part_1(&parsed))));
+            if S::Part2::EXISTS {
+                output.parts.push(part(2, timed(|| S::part_2(&parsed))));
+            }
+        }
+        Err(err) => {
+            output.error = Some(ErrorOutput {
+                line: err.line,
+                column: err.column,
+                expected: err.expected.to_string(),
+                found: err.found.clone(),
+                report: err.report(input).to_string(),
+            });
+        }
+    }
+    output
+}
+
+/// Writes `output` in `format`, the text goes to `writer` and `errors`.
+pub fn write(
+    mut writer: impl Write,
+    mut errors: impl Write,
+    format: Format,
+    output: &DayOutput,
+) -> io::Result<()> {
+    match format {
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut writer, output)?;
+            writeln!(writer)?;
+        }
+        Format::Text => {
+            for warning in &output.warnings {
+                writeln!(errors, "warning: {warning}")?;
+            }
+            if let Some(err) = &output.error {
+                writeln!(errors, "error: {}", err.report)?;
+            }
+            for part in &output.parts {
+                writeln!(writer, "Part {}: {}", part.part, part.answer)?;
+            }
+        }
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day04::Day04, day21::Day21, day25::Day25};
+
+    #[test]
+    fn test_json() {
+        let output = solve::<Day25>(b"a: b c\nb: c");
+        let mut json = Vec::new();
+        write(&mut json, io::sink(), Format::Json, &output).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json["day"], 25);
+        assert!(json["error"].is_null());
+        // Day 25 has no second part.
+        let parts = json["parts"].as_array().unwrap();
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0]["part"], 1);
+        assert_eq!(parts[0]["answer"], "none");
+        assert_eq!(parts[0]["type"], "usize");
+        assert_eq!(parts[0]["nullable"], true);
+
+        // Day 4 answers `none` when the points overflow.
+        let output = solve::<Day04>(b"Card 1: 1 2 | 2 3");
+        assert_eq!(output.parts[0].answer, "1");
+        assert_eq!(output.parts[0].answer_type, "u64");
+        assert!(output.parts[0].nullable);
+    }
+
+    #[test]
+    fn test_error() {
+        let output = solve::<Day21>(b"..\n.S\n..");
+        assert!(output.parts.is_empty());
+        let err = output.error.unwrap();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.expected, "a square map");
+    }
+
+    #[test]
+    fn test_text() {
+        let output = solve::<Day21>(b"...\n.S#\n...");
+        let (mut text, mut errors) = (Vec::new(), Vec::new());
+        write(&mut text, &mut errors, Format::Text, &output).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        assert!(text.starts_with("Part 1: 5\nPart 2: "));
+        let errors = String::from_utf8(errors).unwrap();
+        assert!(errors.starts_with("warning: part 2 "), "{errors}");
+    }
+}
@@ -0,0 +1,260 @@
+//! Helpers for turning malformed puzzle inputs into [`ParseError`]s instead of
+//! panics.
+
+use std::{borrow::Cow, error, fmt, str::FromStr};
+
+use bstr::ByteSlice;
+
+/// Longest excerpt of the input quoted in the `found` part of an error.
+const MAX_FOUND_LEN: usize = 24;
+
+/// The input of a day does not have the expected format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line of the offending input.
+    pub line: usize,
+    /// 1-based byte column of the offending input.
+    pub column: usize,
+    pub expected: Cow<'static, str>,
+    pub found: String,
+    /// Number of bytes of the line covered by the error, for the excerpt.
+    len: usize,
+}
+
+impl ParseError {
+    /// Formats the error together with an excerpt of the offending line of
+    /// `input`, the input the error was created from.
+    pub fn report<'a>(&'a self, input: &'a [u8]) -> Report<'a> {
+        Report { error: self, input }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found {}",
+            self.day, self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// A [`ParseError`] with an excerpt of the input, see [`ParseError::report`].
+///
+/// Meant to follow an `error: ` prefix, like the diagnostics of rustc.
+pub struct Report<'a> {
+    error: &'a ParseError,
+    input: &'a [u8],
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ParseError {
+            day, line, column, ..
+        } = *self.error;
+        let excerpt = self.input.lines().nth(line - 1).unwrap_or_default();
+        let gutter = " ".repeat(line.to_string().len());
+
+        writeln!(
+            f,
+            "expected {}, found {}",
+            self.error.expected, self.error.found
+        )?;
+        writeln!(f, "{gutter}--> day {day}, line {line}, column {column}")?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line} | {}", excerpt.to_str_lossy())?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(column - 1),
+            "^".repeat(self.error.len.max(1))
+        )
+    }
+}
+
+/// Creates [`ParseError`]s pointing into the input of a day.
+///
+/// All slices passed to a parser have to be subslices of its input, which
+/// holds for everything split off by [`ByteSlice`]'s methods. The position of
+/// an error is derived from the address of the slice.
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    day: u8,
+    input: &'a [u8],
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(day: u8, input: &'a [u8]) -> Self {
+        Self { day, input }
+    }
+
+    pub fn input(&self) -> &'a [u8] {
+        self.input
+    }
+
+    /// The empty slice at the end of the input, for errors about missing
+    /// content.
+    pub fn end(&self) -> &'a [u8] {
+        &self.input[self.input.len()..]
+    }
+
+    /// Error pointing at `at`.
+    pub fn error(&self, at: &[u8], expected: impl Into<Cow<'static, str>>) -> ParseError {
+        let offset = (at.as_ptr() as usize)
+            .saturating_sub(self.input.as_ptr() as usize)
+            .min(self.input.len());
+        let line_start = self.input[..offset].rfind_byte(b'\n').map_or(0, |i| i + 1);
+        let rest_of_line = self.input[offset..].lines().next().unwrap_or_default();
+        let at = &at[..at.len().min(rest_of_line.len())];
+
+        let found = match at {
+            [] if offset == self.input.len() => "end of input".to_string(),
+            [] if rest_of_line.is_empty() => "end of line".to_string(),
+            [] => format!("`{}`", rest_of_line[..1].to_str_lossy()),
+            at if at.len() > MAX_FOUND_LEN => {
+                format!("`{}…`", at[..MAX_FOUND_LEN].to_str_lossy())
+            }
+            at => format!("`{}`", at.to_str_lossy()),
+        };
+
+        ParseError {
+            day: self.day,
+            line: self.input[..offset].find_iter(b"\n").count() + 1,
+            column: offset - line_start + 1,
+            expected: expected.into(),
+            found,
+            len: at.len(),
+        }
+    }
+
+    /// Parses `s` as a number of type `T`.
+    pub fn number<T: FromStr>(&self, s: &[u8]) -> Result<T, ParseError> {
+        std::str::from_utf8(s)
+            .ok()
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| self.error(s, "a number"))
+    }
+
+    /// Parses the numbers in `s` separated by `separator`, repeated separators
+    /// are allowed.
+    pub fn numbers<T: FromStr>(&self, s: &[u8], separator: &str) -> Result<Vec<T>, ParseError> {
+        s.split_str(separator)
+            .filter(|n| !n.is_empty())
+            .map(|n| self.number(n))
+            .collect()
+    }
+
+    /// Like [`numbers`](Self::numbers), but expects exactly `N` of them.
+    pub fn number_array<T: FromStr, const N: usize>(
+        &self,
+        s: &[u8],
+        separator: &str,
+    ) -> Result<[T; N], ParseError> {
+        self.numbers(s, separator)?
+            .try_into()
+            .map_err(|_| self.error(s, format!("{N} numbers separated by `{separator}`")))
+    }
+
+    pub fn split_once(
+        &self,
+        s: &'a [u8],
+        separator: &str,
+    ) -> Result<(&'a [u8], &'a [u8]), ParseError> {
+        s.split_once_str(separator)
+            .ok_or_else(|| self.error(s, format!("`{separator}`")))
+    }
+
+    pub fn strip_prefix(&self, s: &'a [u8], prefix: &str) -> Result<&'a [u8], ParseError> {
+        s.strip_prefix(prefix.as_bytes())
+            .ok_or_else(|| self.error(&s[..prefix.len().min(s.len())], format!("`{prefix}`")))
+    }
+
+    pub fn strip_suffix(&self, s: &'a [u8], suffix: &str) -> Result<&'a [u8], ParseError> {
+        s.strip_suffix(suffix.as_bytes()).ok_or_else(|| {
+            self.error(
+                &s[s.len().saturating_sub(suffix.len())..],
+                format!("`{suffix}`"),
+            )
+        })
+    }
+
+    /// Checks that `s` is a rectangular grid of the `allowed` bytes and returns
+    /// its width and height.
+    pub fn grid(&self, s: &[u8], allowed: &[u8]) -> Result<(usize, usize), ParseError> {
+        for line in s.lines() {
+            if let Some(i) = line.iter().position(|c| !allowed.contains(c)) {
+                let allowed = allowed.iter().map(|&c| format!("`{}`", char::from(c)));
+                return Err(self.error(
+                    &line[i..=i],
+                    format!("one of {}", allowed.collect::<Vec<_>>().join(", ")),
+                ));
+            }
+        }
+        self.rectangle(s)
+    }
+
+    /// Checks that all lines of `s` have the same, non-zero length and returns
+    /// the width and height.
+    pub fn rectangle(&self, s: &[u8]) -> Result<(usize, usize), ParseError> {
+        let width = s.lines().next().map_or(0, <[u8]>::len);
+        if width == 0 {
+            return Err(self.error(s, "a grid"));
+        }
+        let mut height = 0;
+        for line in s.lines() {
+            if line.len() != width {
+                let at = &line[width.min(line.len())..];
+                return Err(self.error(at, format!("a line of length {width}")));
+            }
+            height += 1;
+        }
+        Ok((width, height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &[u8] = b"Game 1: 3 blue\nGame 2: x red";
+
+    #[test]
+    fn test_error_position() {
+        let parser = Parser::new(2, INPUT);
+        let (_, draw) = INPUT.lines().nth(1).unwrap().split_once_str(": ").unwrap();
+        let err = parser.number::<u32>(&draw[..1]).unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 9));
+        assert_eq!(err.found, "`x`");
+        assert_eq!(
+            err.report(INPUT).to_string(),
+            "expected a number, found `x`
+ --> day 2, line 2, column 9
+  |
+2 | Game 2: x red
+  |         ^"
+        );
+    }
+
+    #[test]
+    fn test_error_at_end() {
+        let parser = Parser::new(2, INPUT);
+        let err = parser.error(parser.end(), "`;`");
+
+        assert_eq!((err.line, err.column), (2, 14));
+        assert_eq!(err.found, "end of input");
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = |input| Parser::new(3, input).grid(input, b".#");
+        assert_eq!(grid(b"..#\n#.."), Ok((3, 2)));
+        let err = grid(b"..#\n#.").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = grid(b"..#\n#x.").unwrap_err();
+        assert_eq!(err.expected, "one of `.`, `#`");
+    }
+}
@@ -0,0 +1,277 @@
+//! Generic graph searches over implicit graphs, given by a start state and a
+//! successor function.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// Counters describing how much work a search did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States taken off the frontier and passed to the successor function.
+    pub expanded: usize,
+    /// Successors that were new or improved on the best known cost.
+    pub generated: usize,
+}
+
+/// A path found by a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    /// All states from the start to the goal, both included.
+    pub states: Vec<S>,
+}
+
+/// Result of a search, `path` is `None` if no goal is reachable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome<S, C> {
+    pub path: Option<Path<S, C>>,
+    pub stats: Stats,
+}
+
+/// All states reachable within a depth, see [`bfs_distances`].
+#[derive(Debug, Clone)]
+pub struct Reachable<S> {
+    /// Number of steps to each reachable state.
+    pub distances: FnvHashMap<S, usize>,
+    pub stats: Stats,
+}
+
+/// Follows the parent links of `nodes`, `(state, parent index)`, back from
+/// `index` to the start.
+fn reconstruct<S: Clone>(nodes: &[(S, usize)], mut index: usize) -> Vec<S> {
+    let mut states = vec![nodes[index].0.clone()];
+    while nodes[index].1 != usize::MAX {
+        index = nodes[index].1;
+        states.push(nodes[index].0.clone());
+    }
+    states.reverse();
+    states
+}
+
+/// Cheapest path from `start` to a state satisfying `is_goal`, where
+/// `successors` yields the neighbouring states with the cost of the move.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but expands states in order of their cost plus
+/// `heuristic`, which must never overestimate the remaining cost.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    enter_span!("astar");
+    let mut stats = Stats::default();
+    // Every state pushed to the frontier with the index of its parent.
+    let mut nodes = vec![(start.clone(), usize::MAX)];
+    let mut best = FnvHashMap::default();
+    let mut frontier = BinaryHeap::new();
+
+    best.insert(start.clone(), C::default());
+    frontier.push((Reverse(heuristic(&start)), Reverse(C::default()), 0));
+
+    while let Some((_, Reverse(cost), index)) = frontier.pop() {
+        let state = nodes[index].0.clone();
+        if best[&state] < cost {
+            continue;
+        }
+        if is_goal(&state) {
+            return Outcome {
+                path: Some(Path {
+                    cost,
+                    states: reconstruct(&nodes, index),
+                }),
+                stats,
+            };
+        }
+
+        stats.expanded += 1;
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            match best.entry(next.clone()) {
+                Entry::Occupied(entry) if *entry.get() <= next_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert(next_cost);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                }
+            }
+            stats.generated += 1;
+            let estimate = next_cost + heuristic(&next);
+            frontier.push((Reverse(estimate), Reverse(next_cost), nodes.len()));
+            nodes.push((next, index));
+        }
+    }
+
+    Outcome { path: None, stats }
+}
+
+/// Shortest path from `start` to a state satisfying `is_goal` in number of
+/// steps.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut stats = Stats::default();
+    let mut nodes = vec![(start.clone(), usize::MAX)];
+    let mut seen = FnvHashSet::default();
+    let mut queue = VecDeque::from([(0, 0)]);
+    seen.insert(start);
+
+    while let Some((index, depth)) = queue.pop_front() {
+        let state = nodes[index].0.clone();
+        if is_goal(&state) {
+            return Outcome {
+                path: Some(Path {
+                    cost: depth,
+                    states: reconstruct(&nodes, index),
+                }),
+                stats,
+            };
+        }
+
+        stats.expanded += 1;
+        for next in successors(&state) {
+            if seen.insert(next.clone()) {
+                stats.generated += 1;
+                queue.push_back((nodes.len(), depth + 1));
+                nodes.push((next, index));
+            }
+        }
+    }
+
+    Outcome { path: None, stats }
+}
+
+/// Distances to every state reachable from `start` in at most `max_depth`
+/// steps.
+pub fn bfs_distances<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    max_depth: usize,
+) -> Reachable<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut stats = Stats::default();
+    let mut distances = FnvHashMap::default();
+    let mut frontier = vec![start.clone()];
+    distances.insert(start, 0);
+
+    for depth in 0..max_depth {
+        if frontier.is_empty() {
+            break;
+        }
+        enter_span!("bfs layer", depth, states = frontier.len());
+        let mut next_frontier = Vec::new();
+        for state in frontier {
+            stats.expanded += 1;
+            for next in successors(&state) {
+                if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                    entry.insert(depth + 1);
+                    stats.generated += 1;
+                    next_frontier.push(next);
+                }
+            }
+        }
+        frontier = next_frontier;
+    }
+
+    Reachable { distances, stats }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::Parser, Grid};
+
+    const MAZE: &[u8] = b"S.#...
+.##.#.
+...5#.
+.#9.#G";
+
+    fn maze() -> Grid<u8> {
+        Grid::parse(&Parser::new(0, MAZE), MAZE, b"S.#59G").unwrap()
+    }
+
+    fn cost(c: u8) -> u32 {
+        if c.is_ascii_digit() {
+            u32::from(c - b'0')
+        } else {
+            1
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let maze = maze();
+        let goal = maze.position(|&c| c == b'G').unwrap();
+        let successors = |&(x, y): &(usize, usize)| {
+            maze.neighbors_4(x, y)
+                .filter(|&p| maze[p] != b'#')
+                .map(|p| (p, cost(maze[p])))
+                .collect::<Vec<_>>()
+        };
+        let heuristic = |&(x, y): &(usize, usize)| (goal.0.abs_diff(x) + goal.1.abs_diff(y)) as u32;
+
+        let found = dijkstra((0, 0), successors, |&p| p == goal);
+        let path = found.path.unwrap();
+        assert_eq!(path.cost, 16);
+        assert_eq!(path.states.first(), Some(&(0, 0)));
+        assert_eq!(path.states.last(), Some(&goal));
+        assert!(path.states.contains(&(3, 2)));
+
+        let guided = astar((0, 0), successors, heuristic, |&p| p == goal);
+        assert_eq!(guided.path.unwrap().cost, 16);
+        assert!(guided.stats.expanded <= found.stats.expanded);
+
+        assert_eq!(dijkstra((0, 0), successors, |_| false).path, None);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = maze();
+        let successors = |&(x, y): &(usize, usize)| {
+            maze.neighbors_4(x, y)
+                .filter(|&p| maze[p] != b'#')
+                .collect::<Vec<_>>()
+        };
+
+        let path = bfs((0, 0), successors, |&p| maze[p] == b'G').path.unwrap();
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.states.len(), 13);
+
+        let reachable = bfs_distances((0, 0), successors, 2);
+        assert_eq!(reachable.distances.len(), 4);
+        assert_eq!(reachable.distances[&(0, 2)], 2);
+    }
+}
@@ -0,0 +1,149 @@
+//! Runs the whole calendar once and reports where the time goes.
+//!
+//! Unlike [`bench`](crate::bench) every stage runs exactly once, so the
+//! numbers are noisy but add up to the wall clock of solving the season.
+
+use std::{
+    hint::black_box,
+    io::{self, Write},
+    time::Instant,
+};
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::{ParseError, Puzzle};
+
+/// Nanoseconds spent in each stage of a single run of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Timing {
+    pub parse_ns: u64,
+    pub part1_ns: u64,
+    /// `None` for a day without a second part.
+    pub part2_ns: Option<u64>,
+}
+
+impl Timing {
+    pub fn total_ns(&self) -> u64 {
+        self.parse_ns + self.part1_ns + self.part2_ns.unwrap_or(0)
+    }
+}
+
+/// Runs `f` once, returning its result and how long it took.
+pub(crate) fn timed<R>(f: impl FnOnce() -> R) -> (R, u64) {
+    let start = Instant::now();
+    let result = black_box(f());
+    (result, start.elapsed().as_nanos() as u64)
+}
+
+/// The [`Timing`] of one day of a [`Report`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DayTiming {
+    pub day: u8,
+    #[serde(flatten)]
+    pub timing: Timing,
+    pub total_ns: u64,
+    /// Fraction of the summed time of all days, `0.0..=1.0`.
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub days: Vec<DayTiming>,
+    /// Sum of the time of all days.
+    pub total_ns: u64,
+    /// Wall clock of the whole run, below `total_ns` when run in parallel.
+    pub wall_ns: u64,
+    pub parallel: bool,
+}
+
+impl Report {
+    /// The day that took the longest.
+    pub fn slowest(&self) -> Option<&DayTiming> {
+        self.days.iter().max_by_key(|day| day.total_ns)
+    }
+}
+
+/// Solves every puzzle on its input, on all cores if `parallel` is set.
+pub fn run(puzzles: &[(&dyn Puzzle, Vec<u8>)], parallel: bool) -> Result<Report, ParseError> {
+    let time = |(puzzle, input): &(&dyn Puzzle, Vec<u8>)| {
+        puzzle.time(input).map(|timing| (puzzle.day(), timing))
+    };
+    let (timings, wall_ns) = timed(|| {
+        if parallel {
+            puzzles.par_iter().map(time).collect::<Result<Vec<_>, _>>()
+        } else {
+            puzzles.iter().map(time).collect()
+        }
+    });
+    let timings = timings?;
+
+    let total_ns = timings
+        .iter()
+        .map(|(_, timing)| timing.total_ns())
+        .sum::<u64>();
+    let days = timings
+        .into_iter()
+        .map(|(day, timing)| DayTiming {
+            day,
+            timing,
+            total_ns: timing.total_ns(),
+            share: timing.total_ns() as f64 / total_ns.max(1) as f64,
+        })
+        .collect();
+    Ok(Report {
+        days,
+        total_ns,
+        wall_ns,
+        parallel,
+    })
+}
+
+pub fn write_json(writer: impl Write, report: &Report) -> io::Result<()> {
+    serde_json::to_writer_pretty(writer, report).map_err(io::Error::from)
+}
+
+pub fn write_csv(mut writer: impl Write, report: &Report) -> io::Result<()> {
+    writeln!(writer, "day,parse_ns,part1_ns,part2_ns,total_ns,share")?;
+    for d in &report.days {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{:.4}",
+            d.day,
+            d.timing.parse_ns,
+            d.timing.part1_ns,
+            d.timing.part2_ns.map_or(String::new(), |ns| ns.to_string()),
+            d.total_ns,
+            d.share
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day01::Day01, day06::Day06, day25::Day25};
+
+    #[test]
+    fn test_run() {
+        let puzzles: [(&dyn Puzzle, Vec<u8>); 2] = [
+            (&Day01, b"1abc2\npqr3stu8vwx".to_vec()),
+            (
+                &Day06,
+                b"Time:      7  15   30\nDistance:  9  40  200".to_vec(),
+            ),
+        ];
+        for parallel in [false, true] {
+            let report = run(&puzzles, parallel).unwrap();
+            assert_eq!(report.days.len(), 2);
+            assert_eq!(report.days[1].day, 6);
+            let shares = report.days.iter().map(|day| day.share).sum::<f64>();
+            assert!((shares - 1.0).abs() < 1e-9 || report.total_ns == 0);
+            assert!(report.slowest().is_some());
+        }
+
+        let broken: [(&dyn Puzzle, Vec<u8>); 1] = [(&Day25, b"a b".to_vec())];
+        assert_eq!(run(&broken, false).unwrap_err().day, 25);
+    }
+}
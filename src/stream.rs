@@ -0,0 +1,208 @@
+//! Solving the line oriented days straight from an [`io::BufRead`].
+//!
+//! The `solve_stream` functions of these days hold a single line of the input
+//! at a time plus whatever state the puzzle needs, so generated inputs larger
+//! than memory can be solved too. Their answers match those of `part_1` and
+//! `part_2` on the same input.
+
+use std::{
+    borrow::Cow,
+    error, fmt,
+    io::{self, BufRead},
+};
+
+use bstr::ByteSlice;
+
+use crate::{
+    day01, day02, day04, day07, day09, day12, day15, day18, parse::Parser, Answer, Answers,
+    ParseError,
+};
+
+/// The days with a `solve_stream` function.
+pub const DAYS: [u8; 8] = [1, 2, 4, 7, 9, 12, 15, 18];
+
+/// Reading a streamed input failed, or it is malformed.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read input: {err}"),
+            Self::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+/// Position of the end of a streamed input, 1-based like in a
+/// [`ParseError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct End {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl End {
+    /// Error about content missing at the end of the input, like an error at
+    /// [`Parser::end`] of a whole input.
+    pub fn error(&self, day: u8, expected: impl Into<Cow<'static, str>>) -> ParseError {
+        let mut err = Parser::new(day, &[]).error(&[], expected);
+        (err.line, err.column) = (self.line, self.column);
+        err
+    }
+}
+
+/// Calls `f` for every record of `reader` ending in `delimiter`, together
+/// with a parser over just that record. A line break at the end of a record
+/// is stripped, so with `b'\n'` the records are the lines of the input.
+///
+/// The errors returned by `f` are moved to their position in the whole
+/// input.
+pub fn for_each_record(
+    day: u8,
+    mut reader: impl BufRead,
+    delimiter: u8,
+    mut f: impl FnMut(&Parser, &[u8]) -> Result<(), ParseError>,
+) -> Result<End, StreamError> {
+    let mut buf = Vec::new();
+    // Where the current record starts and where the last one ended, 0-based.
+    let (mut line, mut column) = (0, 0);
+    let mut end = End { line: 1, column: 1 };
+    loop {
+        buf.clear();
+        if reader.read_until(delimiter, &mut buf)? == 0 {
+            return Ok(end);
+        }
+        let mut record = buf.strip_suffix(&[delimiter]).unwrap_or(&buf);
+        record = record.strip_suffix(b"\n").unwrap_or(record);
+        record = record.strip_suffix(b"\r").unwrap_or(record);
+
+        f(&Parser::new(day, record), record).map_err(|mut err| {
+            if err.line == 1 {
+                err.column += column;
+            }
+            err.line += line;
+            err
+        })?;
+
+        end = advance(line, column, record);
+        let next = advance(line, column, &buf);
+        (line, column) = (next.line - 1, next.column - 1);
+    }
+}
+
+/// Where `bytes` starting at the 0-based `line` and `column` end, 1-based.
+fn advance(line: usize, column: usize, bytes: &[u8]) -> End {
+    match bytes.rfind_byte(b'\n') {
+        Some(i) => End {
+            line: line + bytes.find_iter(b"\n").count() + 1,
+            column: bytes.len() - i,
+        },
+        None => End {
+            line: line + 1,
+            column: column + bytes.len() + 1,
+        },
+    }
+}
+
+/// Solves both parts of `day` from `reader`, `None` for the days not in
+/// [`DAYS`].
+pub fn solve(day: u8, reader: impl BufRead) -> Option<Result<Answers, StreamError>> {
+    fn answers((part_1, part_2): (impl Answer, impl Answer)) -> Answers {
+        Answers {
+            part_1: Some(part_1.to_answer()),
+            part_2: Some(part_2.to_answer()),
+        }
+    }
+
+    Some(match day {
+        1 => day01::solve_stream(reader).map(answers),
+        2 => day02::solve_stream(reader).map(answers),
+        4 => day04::solve_stream(reader).map(answers),
+        7 => day07::solve_stream(reader).map(answers),
+        9 => day09::solve_stream(reader).map(answers),
+        12 => day12::solve_stream(reader).map(answers),
+        15 => day15::solve_stream(reader).map(answers),
+        18 => day18::solve_stream(reader).map(answers),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::answers;
+
+    fn error_at(input: &[u8], delimiter: u8) -> ParseError {
+        let err = for_each_record(0, input, delimiter, |parser, record| {
+            parser.number::<u8>(record).map(drop)
+        });
+        match err {
+            Err(StreamError::Parse(err)) => err,
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_error_position() {
+        let err = error_at(b"1\n2\r\n3\n4x\n5", b'\n');
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(err.found, "`4x`");
+        let err = error_at(b"2,13,x,6\n", b',');
+        assert_eq!((err.line, err.column), (1, 6));
+        assert_eq!(err.found, "`x`");
+    }
+
+    #[test]
+    fn test_end() {
+        let end = |input: &[u8]| for_each_record(0, input, b'\n', |_, _| Ok(())).unwrap();
+        assert_eq!(end(b""), End { line: 1, column: 1 });
+        assert_eq!(end(b"ab\r\ncd\n"), End { line: 2, column: 3 });
+    }
+
+    #[test]
+    fn test_examples() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+        for example in answers::read_examples(&dir).unwrap() {
+            if !DAYS.contains(&example.day) {
+                continue;
+            }
+            let input = crate::load_input(Some(example.input.as_os_str()), None).unwrap();
+            let expected = crate::puzzle(example.day).unwrap().run(&input, None);
+            let answers = solve(example.day, &input[..]).unwrap();
+            assert_eq!(
+                answers.ok(),
+                expected.ok(),
+                "day {} with {}",
+                example.day,
+                example.input_id
+            );
+        }
+    }
+}
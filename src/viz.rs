@@ -0,0 +1,378 @@
+//! Pictures of the maps of the grid days, with what their solutions find
+//! drawn on top.
+//!
+//! The days on maps have a `visualize` function that draws the states their
+//! solution goes through as a sequence of [`Frame`]s into a [`Sink`]: numbered
+//! image files with [`Images`], or an animation in a terminal with [`Ansi`].
+//! `aoc viz` runs them.
+
+use std::{
+    error, fmt, fs,
+    io::{self, Write},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use crate::{day10, day14, day16, day17, day21, day23, Grid, ParseError};
+
+/// The days with a `visualize` function.
+pub const DAYS: [u8; 6] = [10, 14, 16, 17, 21, 23];
+
+/// A colour as its red, green and blue components.
+pub type Rgb = [u8; 3];
+
+// The days share their colours so that their pictures look alike.
+/// Empty tiles.
+pub const BACKGROUND: Rgb = [24, 24, 32];
+/// Whatever is on the map: walls, rocks, mirrors or pipes.
+pub const FEATURE: Rgb = [110, 110, 124];
+/// The loop, path or rocks the solution moves along.
+pub const PATH: Rgb = [240, 190, 60];
+/// Tiles a search reached or that are counted in the answer.
+pub const REACHED: Rgb = [70, 170, 100];
+/// Single points like the start.
+pub const MARK: Rgb = [220, 60, 50];
+
+/// A picture of a map, one pixel per tile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub pixels: Grid<Rgb>,
+}
+
+impl Frame {
+    /// Picture of `grid` with each tile in the colour `colour` picks for it.
+    pub fn new<T>(grid: &Grid<T>, colour: impl FnMut(&T) -> Rgb) -> Self {
+        Self {
+            pixels: grid.map(colour),
+        }
+    }
+
+    /// Paints the tiles at `positions` over in `colour`.
+    pub fn paint(
+        &mut self,
+        positions: impl IntoIterator<Item = (usize, usize)>,
+        colour: Rgb,
+    ) -> &mut Self {
+        for position in positions {
+            self.pixels[position] = colour;
+        }
+        self
+    }
+
+    /// Red, green and blue bytes of the picture with each tile as a square
+    /// of `scale` by `scale` pixels, row by row.
+    fn scaled(&self, scale: usize) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(3 * self.pixels.cells().len() * scale * scale);
+        for row in self.pixels.rows() {
+            let start = bytes.len();
+            for pixel in row {
+                for _ in 0..scale {
+                    bytes.extend_from_slice(pixel);
+                }
+            }
+            let end = bytes.len();
+            for _ in 1..scale {
+                bytes.extend_from_within(start..end);
+            }
+        }
+        bytes
+    }
+}
+
+/// Where the frames of a visualization go.
+pub trait Sink {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()>;
+}
+
+/// Collects the frames, to look at them in code.
+impl Sink for Vec<Frame> {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        self.push(frame.clone());
+        Ok(())
+    }
+}
+
+/// File format of the frames written by [`Images`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Binary PPM, which most image tools read.
+    Ppm,
+    /// Needs the `png` feature.
+    #[cfg(feature = "png")]
+    Png,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Ppm => "ppm",
+            #[cfg(feature = "png")]
+            Self::Png => "png",
+        }
+    }
+}
+
+/// Writes `frame` as a binary PPM, with each tile `scale` pixels wide.
+pub fn write_ppm(mut writer: impl Write, frame: &Frame, scale: usize) -> io::Result<()> {
+    let (width, height) = (frame.pixels.width(), frame.pixels.height());
+    write!(writer, "P6\n{} {}\n255\n", width * scale, height * scale)?;
+    writer.write_all(&frame.scaled(scale))
+}
+
+/// Writes `frame` as a PNG, with each tile `scale` pixels wide.
+#[cfg(feature = "png")]
+pub fn write_png(writer: impl Write, frame: &Frame, scale: usize) -> io::Result<()> {
+    let (width, height) = (frame.pixels.width(), frame.pixels.height());
+    let size = |tiles: usize| {
+        u32::try_from(tiles * scale)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame too large for a PNG"))
+    };
+    let mut encoder = png::Encoder::new(writer, size(width)?, size(height)?);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut png| png.write_image_data(&frame.scaled(scale)))
+        .map_err(|err| match err {
+            png::EncodingError::IoError(err) => err,
+            err => io::Error::other(err),
+        })
+}
+
+/// Writes every frame to its own file in a directory, numbered from
+/// `frame-0000` on.
+#[derive(Debug)]
+pub struct Images {
+    dir: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    count: usize,
+}
+
+impl Images {
+    /// Creates `dir` if it doesn't exist yet, with each tile `scale` pixels
+    /// wide in the images.
+    pub fn new(dir: impl Into<PathBuf>, format: ImageFormat, scale: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            format,
+            scale,
+            count: 0,
+        })
+    }
+
+    /// Number of frames written so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Sink for Images {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        let name = format!("frame-{:04}.{}", self.count, self.format.extension());
+        let mut writer = io::BufWriter::new(fs::File::create(self.dir.join(name))?);
+        match self.format {
+            ImageFormat::Ppm => write_ppm(&mut writer, frame, self.scale)?,
+            #[cfg(feature = "png")]
+            ImageFormat::Png => write_png(&mut writer, frame, self.scale)?,
+        }
+        writer.flush()?;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// Draws the frames over each other as an animation in a terminal with 24-bit
+/// colour, two tiles above each other per character.
+#[derive(Debug)]
+pub struct Ansi<W> {
+    writer: W,
+    delay: Duration,
+    /// Lines taken by the last frame, to move back up over it.
+    lines: usize,
+}
+
+impl<W: Write> Ansi<W> {
+    /// Waits `delay` after each frame.
+    pub fn new(writer: W, delay: Duration) -> Self {
+        Self {
+            writer,
+            delay,
+            lines: 0,
+        }
+    }
+}
+
+impl<W: Write> Sink for Ansi<W> {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        let mut out = Vec::new();
+        if self.lines > 0 {
+            write!(out, "\x1b[{}A\r", self.lines)?;
+        }
+
+        let pixels = &frame.pixels;
+        for y in (0..pixels.height()).step_by(2) {
+            let mut last = None;
+            for x in 0..pixels.width() {
+                // The upper half block shows the top tile in the foreground
+                // colour and the bottom one in the background colour.
+                let colours = (pixels[(x, y)], pixels.get(x, y + 1).copied());
+                if last != Some(colours) {
+                    let ([r, g, b], bottom) = colours;
+                    write!(out, "\x1b[38;2;{r};{g};{b}m")?;
+                    match bottom {
+                        Some([r, g, b]) => write!(out, "\x1b[48;2;{r};{g};{b}m")?,
+                        None => write!(out, "\x1b[49m")?,
+                    }
+                    last = Some(colours);
+                }
+                out.extend_from_slice("▀".as_bytes());
+            }
+            out.extend_from_slice(b"\x1b[0m\n");
+        }
+        self.lines = pixels.height().div_ceil(2);
+
+        self.writer.write_all(&out)?;
+        self.writer.flush()?;
+        thread::sleep(self.delay);
+        Ok(())
+    }
+}
+
+/// Visualizing failed because the input is malformed or a frame couldn't be
+/// written.
+#[derive(Debug)]
+pub enum VizError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for VizError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to write frame: {err}"),
+            Self::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for VizError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for VizError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseError> for VizError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+/// Draws the solution of `day` on `input` into `sink`, `None` for the days
+/// not in [`DAYS`].
+pub fn visualize(day: u8, input: &[u8], sink: &mut dyn Sink) -> Option<Result<(), VizError>> {
+    Some(match day {
+        10 => day10::visualize(input, sink),
+        14 => day14::visualize(input, sink),
+        16 => day16::visualize(input, sink),
+        17 => day17::visualize(input, sink),
+        21 => day21::visualize(input, sink),
+        23 => day23::visualize(input, sink),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::answers;
+
+    fn frame() -> Frame {
+        let mut frame = Frame::new(&Grid::from_vec(2, vec![0, 1, 1, 0]), |&tile| {
+            if tile == 1 {
+                FEATURE
+            } else {
+                BACKGROUND
+            }
+        });
+        frame.paint([(0, 0)], MARK);
+        frame
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let mut ppm = Vec::new();
+        write_ppm(&mut ppm, &frame(), 3).unwrap();
+        let header = b"P6\n6 6\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = ppm[header.len()..].chunks(3).collect::<Vec<_>>();
+        assert_eq!(pixels.len(), 36);
+        let (top, bottom) = pixels.split_at(18);
+        assert_eq!(top[..6], [
+            &MARK, &MARK, &MARK, &FEATURE, &FEATURE, &FEATURE
+        ]);
+        assert!(top.chunks(6).all(|row| row == &top[..6]));
+        assert_eq!(bottom[..6], [
+            &FEATURE,
+            &FEATURE,
+            &FEATURE,
+            &BACKGROUND,
+            &BACKGROUND,
+            &BACKGROUND
+        ]);
+        assert!(bottom.chunks(6).all(|row| row == &bottom[..6]));
+    }
+
+    #[test]
+    fn test_ansi() {
+        let mut out = Vec::new();
+        let mut ansi = Ansi::new(&mut out, Duration::ZERO);
+        ansi.frame(&frame()).unwrap();
+        ansi.frame(&frame()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        // Both rows fit on one line, which the second frame moves back up.
+        assert_eq!(out.matches('\n').count(), 2);
+        assert_eq!(out.matches("▀").count(), 4);
+        assert_eq!(out.matches("\x1b[1A\r").count(), 1);
+    }
+
+    #[test]
+    fn test_examples() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+        for example in answers::read_examples(&dir).unwrap() {
+            if !DAYS.contains(&example.day) {
+                continue;
+            }
+            let input = crate::load_input(Some(example.input.as_os_str()), None).unwrap();
+            let mut frames = Vec::new();
+            visualize(example.day, &input, &mut frames)
+                .unwrap()
+                .unwrap();
+            assert!(!frames.is_empty(), "day {}", example.day);
+            let grid = Grid::parse_any(&crate::parse::Parser::new(0, &input), &input).unwrap();
+            for frame in frames {
+                assert_eq!(
+                    (frame.pixels.width(), frame.pixels.height()),
+                    (grid.width(), grid.height()),
+                    "day {} with {}",
+                    example.day,
+                    example.input_id
+                );
+            }
+        }
+    }
+}
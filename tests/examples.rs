@@ -0,0 +1,98 @@
+//! Runs every day against each input of the example corpus in `examples/`,
+//! see [`aoc_2023::answers`] for the layout.
+//!
+//! A libtest-like runner rather than `#[test]` functions, so a new example
+//! only needs its files. Every part is its own case, named like
+//! `day05/example::part_2`, and the usual name filters and `--skip` work.
+
+use std::{path::Path, process::ExitCode};
+
+use aoc_2023::{
+    answers::{self, Status},
+    Part,
+};
+
+fn main() -> ExitCode {
+    let mut filters = Vec::new();
+    let mut skips = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--skip" => skips.extend(args.next()),
+            // Flags of libtest, like `--nocapture`, don't apply here.
+            flag if flag.starts_with('-') => (),
+            _ => filters.push(arg),
+        }
+    }
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+    let examples = answers::read_examples(&dir).expect("failed to read the example corpus");
+    let matches = |name: &str| {
+        (filters.is_empty() || filters.iter().any(|filter| name.contains(filter.as_str())))
+            && !skips.iter().any(|skip| name.contains(skip.as_str()))
+    };
+
+    let cases = examples
+        .iter()
+        .flat_map(|example| {
+            [(Part::One, &example.part_1), (Part::Two, &example.part_2)]
+                .into_iter()
+                .filter(|(_, answer)| answer.is_some())
+                .map(move |(part, _)| {
+                    let name = format!(
+                        "day{:02}/{}::part_{}",
+                        example.day,
+                        example.input_id,
+                        if part == Part::One { 1 } else { 2 }
+                    );
+                    (name, example, part)
+                })
+        })
+        .collect::<Vec<_>>();
+    let filtered_out = cases.iter().filter(|(name, ..)| !matches(name)).count();
+
+    println!("\nrunning {} examples", cases.len() - filtered_out);
+    let mut failures = Vec::new();
+    for (name, example, part) in cases.iter().filter(|(name, ..)| matches(name)) {
+        let input = aoc_2023::load_input(Some(example.input.as_os_str()), None)
+            .unwrap_or_else(|err| panic!("failed to read {}: {err}", example.input.display()));
+        let puzzle = aoc_2023::puzzle(example.day).unwrap();
+        // Only the answer of this case, so the other part is not run.
+        let mut case = (*example).clone();
+        match part {
+            Part::One => case.part_2 = None,
+            Part::Two => case.part_1 = None,
+        }
+        let status = answers::check_known(puzzle, &case, &input)
+            .pop()
+            .map_or_else(
+                || Status::Failed("the day has no such part".to_string()),
+                |check| check.status,
+            );
+
+        if status == Status::Correct {
+            println!("example {name} ... ok");
+        } else {
+            println!("example {name} ... FAILED");
+            failures.push((name, status));
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (name, status) in &failures {
+            println!("    {name}: {status}");
+        }
+    }
+    println!(
+        "\nexample result: {}. {} passed; {} failed; {filtered_out} filtered out\n",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        cases.len() - filtered_out - failures.len(),
+        failures.len()
+    );
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}